@@ -1,60 +1,62 @@
-use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
-pub fn find_source_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
-    let mut found_files = Vec::new();
+use ignore::{WalkBuilder, WalkState};
 
-    match fs::read_dir(dir) {
-        Ok(entries) => {
-            for entry in entries.flatten() {
-                let path = entry.path();
+use super::notebook::is_notebook;
+use super::parser::{detect_language, Language};
 
-                if path.is_dir() {
-                    let mut sub_files = find_source_files(&path, extension);
-                    found_files.append(&mut sub_files);
-                } else if path.is_file() {
-                    if path.extension().and_then(|s| s.to_str()) == Some(extension) {
-                        found_files.push(path);
-                    }
-                }
-            }
-        }
-
-        Err(e) => eprintln!("Error reading directory: {}", e),
-    }
-
-    found_files
+pub struct WalkOptions {
+    pub use_ignore_files: bool, // honor .gitignore, .ignore and .fuzcignore files
+    pub threads: usize,
 }
 
-pub fn find_all_source_files(dir: &Path) -> Vec<PathBuf> {
-    let mut found_files = Vec::new();
+pub const FUZC_IGNORE_FILENAME: &str = ".fuzcignore";
 
-    match fs::read_dir(dir) {
-        Ok(entries) => {
-            for entry in entries.flatten() {
-                let path = entry.path();
+// walks in parallel, the result is sorted so the order is the same on every run
+pub fn find_all_source_files(dir: &Path, options: &WalkOptions) -> Vec<PathBuf> {
+    let found_files = Mutex::new(Vec::new());
 
-                if path.is_dir() {
-                    if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-                        if should_skip_directory(dir_name) {
-                            continue;
-                        }
-                    }
+    let mut builder = WalkBuilder::new(dir);
+    builder
+        .hidden(false)
+        .parents(options.use_ignore_files)
+        .ignore(options.use_ignore_files)
+        .git_ignore(options.use_ignore_files)
+        .git_global(options.use_ignore_files)
+        .git_exclude(options.use_ignore_files)
+        .require_git(false)
+        .threads(options.threads)
+        .filter_entry(|entry| {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            let skip = is_dir
+                && entry.depth() > 0
+                && entry.file_name().to_str().is_some_and(should_skip_directory);
+            !skip
+        });
 
-                    let mut sub_files = find_all_source_files(&path);
-                    found_files.append(&mut sub_files);
+    if options.use_ignore_files {
+        builder.add_custom_ignore_filename(FUZC_IGNORE_FILENAME);
+    }
 
-                } else if path.is_file() {
-                    if path.extension().is_some() {
-                        found_files.push(path);
+    builder.build_parallel().run(|| {
+        Box::new(|entry| {
+            match entry {
+                Ok(entry) => {
+                    let is_file = entry.file_type().is_some_and(|t| t.is_file());
+                    if is_file {
+                        found_files.lock().unwrap().push(entry.into_path());
                     }
                 }
-            }
-        }
 
-        Err(e) => eprintln!("Error reading directory: {}", e),
-    }
+                Err(e) => eprintln!("Error reading directory: {}", e),
+            }
+            WalkState::Continue
+        })
+    });
 
+    let mut found_files = found_files.into_inner().unwrap();
+    found_files.sort();
     found_files
 }
 
@@ -71,5 +73,20 @@ fn should_skip_directory(dir_name: &str) -> bool {
             | "bin"
             | "obj"
             | "__pycache__"
+            | ".ipynb_checkpoints"
+            | ".fuzc"
     )
 }
+
+// keeps only files in one of `languages` (or any supported language if empty).
+// files without an extension are kept too, they may be scripts with a shebang,
+// and so are notebooks, whose language is only known once they're loaded
+pub fn filter_by_language(paths: Vec<PathBuf>, languages: &[Language]) -> Vec<PathBuf> {
+    paths
+        .into_iter()
+        .filter(|path| match detect_language(path) {
+            Some(language) => languages.is_empty() || languages.contains(&language),
+            None => path.extension().is_none() || is_notebook(path),
+        })
+        .collect()
+}
@@ -1,40 +1,99 @@
-use super::parser::{detect_language, extract_comments_from_content};
+use std::path::Path;
+
+use super::index::CommentIndex;
+use super::notebook::{self, CellKind};
+use super::parallel;
+use super::parser::{extract_comments_from_content, CommentMatch, CommentType, Language};
 use super::source::SourceFile;
+use super::tags::{parse_tags, DocTag};
 
 pub struct Comment<'a> {
+    pub cell: Option<usize>, // 1-based notebook cell, `line` then counts from the cell's first line
     pub line: usize,
+    pub column: usize, // 1-based, in chars
     pub text: &'a str,
     pub file_name: &'a str,
+    pub file_path: &'a Path,
+    pub language: Language,
+    pub kind: CommentType,
+    pub tags: Vec<DocTag>, // block tags of doc comments and docstrings
+}
+
+impl Comment<'_> {
+    // `#cell:N` for comments in notebooks, appended to the file in locations
+    pub fn cell_anchor(&self) -> String {
+        self.cell.map(|cell| format!("#cell:{}", cell)).unwrap_or_default()
+    }
 }
 
-pub fn extract_comments<'a>(files: &'a [SourceFile]) -> Vec<Comment<'a>> {
+// files are parsed in parallel, comments keep the order of `files`.
+// files with an up to date entry in `index` aren't parsed again.
+// Notebook markdown cells are only kept with `markdown_cells`
+pub fn extract_comments<'a>(
+    files: &'a [SourceFile],
+    threads: usize,
+    index: Option<&CommentIndex>,
+    markdown_cells: bool,
+) -> Vec<Comment<'a>> {
+    parallel::flat_map(files, threads, |file| extract_file_comments(file, index, markdown_cells))
+}
+
+pub fn parse_file(file: &SourceFile) -> Vec<CommentMatch> {
+    if file.cells.is_empty() {
+        extract_comments_from_content(&file.content, file.language)
+    } else {
+        notebook::extract_comments(&file.content, &file.cells, file.language)
+    }
+}
+
+fn extract_file_comments<'a>(file: &'a SourceFile, index: Option<&CommentIndex>, markdown_cells: bool) -> Vec<Comment<'a>> {
     let mut comments = Vec::new();
+    let mut line_offsets = vec![0];
+    let language = file.language;
 
-    for file in files {
-        let mut line_offsets = vec![0];
+    let comment_matches = match index.and_then(|index| index.lookup(file, language)) {
+        Some(cached) => cached.to_vec(),
+        None => parse_file(file),
+    };
 
-        if let Some(language) = detect_language(&file.path) {
-            let comment_matches = extract_comments_from_content(&file.content, language);
+    for (i, c) in file.content.char_indices() {
+        if c == '\n' {
+            line_offsets.push(i + 1);
+        }
+    }
+    for comment_match in comment_matches {
+        let text = &file.content[comment_match.start_byte..comment_match.end_byte];
+        let line_num = match line_offsets.binary_search(&comment_match.start_byte) {
+            Ok(idx) => idx + 1,
+            Err(idx) => idx,
+        };
 
-            for (i, c) in file.content.char_indices() {
-                if c == '\n' {
-                    line_offsets.push(i + 1);
-                }
-            }
-            for comment_match in comment_matches {
-                let text = &file.content[comment_match.start_byte..comment_match.end_byte];
-                let line_num = match line_offsets.binary_search(&comment_match.start_byte) {
-                    Ok(idx) => idx + 1,
-                    Err(idx) => idx,
-                };
-
-                comments.push(Comment {
-                    line: line_num,
-                    text: text,
-                    file_name: &file.name,
-                });
+        let line_start = line_offsets[line_num - 1];
+        let column = file.content[line_start..comment_match.start_byte].chars().count() + 1;
+
+        let (cell, line_num) = if file.cells.is_empty() {
+            (None, line_num)
+        } else {
+            let cell = notebook::cell_at(&file.cells, comment_match.start_byte);
+            if file.cells[cell].kind == CellKind::Markdown && !markdown_cells {
+                continue;
             }
-        }
+            // cells start at the beginning of a line
+            let first_line = line_offsets.partition_point(|&offset| offset <= file.cells[cell].start);
+            (Some(cell + 1), line_num - first_line + 1)
+        };
+
+        comments.push(Comment {
+            cell,
+            line: line_num,
+            column,
+            text,
+            file_name: &file.name,
+            file_path: &file.path,
+            language,
+            kind: comment_match.comment_type,
+            tags: parse_tags(text, comment_match.comment_type),
+        });
     }
     comments
 }
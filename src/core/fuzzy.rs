@@ -0,0 +1,271 @@
+// fzf-style fuzzy matching: pattern chars must appear in order, scored by
+// how "tight" the match is and whether chars land on word boundaries
+
+const SCORE_MATCH: i32 = 16;
+const SCORE_GAP_START: i32 = -3;
+const SCORE_GAP_EXTENSION: i32 = -1;
+
+const BONUS_BOUNDARY: i32 = SCORE_MATCH / 2;
+const BONUS_BOUNDARY_WHITE: i32 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i32 = BONUS_BOUNDARY + 1;
+const BONUS_NON_WORD: i32 = SCORE_MATCH / 2;
+const BONUS_CAMEL_123: i32 = BONUS_BOUNDARY + SCORE_GAP_EXTENSION;
+const BONUS_CONSECUTIVE: i32 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+// above this many cells the full DP gets too expensive, use the greedy match
+const MAX_DP_CELLS: usize = 100_000;
+
+#[derive(Debug, Clone)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    pub positions: Vec<usize>, // byte offsets of the matched chars in the text
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CharClass {
+    White,
+    NonWord,
+    Delimiter,
+    Lower,
+    Upper,
+    Number,
+}
+
+fn char_class(ch: char) -> CharClass {
+    if ch.is_lowercase() {
+        CharClass::Lower
+    } else if ch.is_uppercase() {
+        CharClass::Upper
+    } else if ch.is_numeric() {
+        CharClass::Number
+    } else if ch.is_alphabetic() {
+        CharClass::Lower // letters without case (CJK etc.)
+    } else if ch.is_whitespace() {
+        CharClass::White
+    } else if matches!(ch, '/' | ',' | ':' | ';' | '|' | '.' | '-' | '_') {
+        CharClass::Delimiter
+    } else {
+        CharClass::NonWord
+    }
+}
+
+fn is_word(class: CharClass) -> bool {
+    matches!(class, CharClass::Lower | CharClass::Upper | CharClass::Number)
+}
+
+fn bonus_for(prev: CharClass, curr: CharClass) -> i32 {
+    if is_word(curr) {
+        match prev {
+            CharClass::White => return BONUS_BOUNDARY_WHITE,
+            CharClass::Delimiter => return BONUS_BOUNDARY_DELIMITER,
+            CharClass::NonWord => return BONUS_BOUNDARY,
+            _ => {}
+        }
+    }
+
+    if (prev == CharClass::Lower && curr == CharClass::Upper)
+        || (prev != CharClass::Number && curr == CharClass::Number)
+    {
+        return BONUS_CAMEL_123;
+    }
+
+    match curr {
+        CharClass::NonWord | CharClass::Delimiter => BONUS_NON_WORD,
+        CharClass::White => BONUS_BOUNDARY_WHITE,
+        _ => 0,
+    }
+}
+
+fn fold(ch: char) -> char {
+    ch.to_lowercase().next().unwrap_or(ch)
+}
+
+/// Case-insensitive fuzzy match of `pattern` against `text`.
+/// Returns `None` if the pattern is not a subsequence of the text.
+pub fn fuzzy_match(text: &str, pattern: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern.chars().map(fold).collect();
+    if pattern.is_empty() {
+        return None;
+    }
+
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let folded: Vec<char> = chars.iter().map(|&(_, ch)| fold(ch)).collect();
+
+    // forward pass: find where the earliest possible match starts and ends
+    let mut pi = 0;
+    let mut start = None;
+    let mut end = 0;
+    for (i, &ch) in folded.iter().enumerate() {
+        if ch == pattern[pi] {
+            if pi == 0 {
+                start = Some(i);
+            }
+            pi += 1;
+            if pi == pattern.len() {
+                end = i;
+                break;
+            }
+        }
+    }
+    if pi < pattern.len() {
+        return None;
+    }
+    let start = start?;
+
+    // backward pass from the end of the text to find the latest possible end
+    let mut pi = pattern.len();
+    for (i, &ch) in folded.iter().enumerate().rev() {
+        if ch == pattern[pi - 1] {
+            if pi == pattern.len() {
+                end = end.max(i);
+            }
+            pi -= 1;
+            if pi == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut bonuses = Vec::with_capacity(end - start + 1);
+    let mut prev_class = if start == 0 {
+        CharClass::White
+    } else {
+        char_class(chars[start - 1].1)
+    };
+    for &(_, ch) in &chars[start..=end] {
+        let class = char_class(ch);
+        bonuses.push(bonus_for(prev_class, class));
+        prev_class = class;
+    }
+
+    let window = &folded[start..=end];
+    let matched = if window.len() * pattern.len() > MAX_DP_CELLS {
+        greedy_match(window, &pattern, &bonuses)
+    } else {
+        optimal_match(window, &pattern, &bonuses)
+    }?;
+
+    Some(FuzzyMatch {
+        score: matched.0,
+        positions: matched.1.iter().map(|&i| chars[start + i].0).collect(),
+    })
+}
+
+// Smith-Waterman style DP: best[i][j] is the best score with pattern[i]
+// matched at window[j]. Returns the score and the window indices matched.
+fn optimal_match(window: &[char], pattern: &[char], bonuses: &[i32]) -> Option<(i32, Vec<usize>)> {
+    let n = window.len();
+    let m = pattern.len();
+    let unreachable = i32::MIN / 2;
+
+    let mut best = vec![unreachable; m * n];
+    let mut run_bonus = vec![0; m * n]; // bonus at the start of the consecutive run
+    let mut from = vec![usize::MAX; m * n];
+
+    for (j, &ch) in window.iter().enumerate() {
+        if ch == pattern[0] {
+            best[j] = SCORE_MATCH + bonuses[j] * BONUS_FIRST_CHAR_MULTIPLIER;
+            run_bonus[j] = bonuses[j];
+        }
+    }
+
+    for (i, &pattern_ch) in pattern.iter().enumerate().skip(1) {
+        let row = i * n;
+        let prev_row = (i - 1) * n;
+
+        // best (score + gap offset) seen so far in the previous row, for gapped transitions
+        let mut gap_best = unreachable;
+        let mut gap_from = usize::MAX;
+
+        for j in i..n {
+            if j >= 2 {
+                let k = j - 2;
+                let candidate = best[prev_row + k] - SCORE_GAP_EXTENSION * k as i32;
+                if best[prev_row + k] > unreachable && candidate > gap_best {
+                    gap_best = candidate;
+                    gap_from = k;
+                }
+            }
+
+            if window[j] != pattern_ch {
+                continue;
+            }
+
+            let mut score = unreachable;
+
+            if gap_from != usize::MAX {
+                let gapped = gap_best + SCORE_GAP_START + SCORE_GAP_EXTENSION * (j as i32 - 2)
+                    + SCORE_MATCH + bonuses[j];
+                if gapped > score {
+                    score = gapped;
+                    from[row + j] = gap_from;
+                    run_bonus[row + j] = bonuses[j];
+                }
+            }
+
+            let prev = best[prev_row + j - 1];
+            if prev > unreachable {
+                let bonus = bonuses[j]
+                    .max(BONUS_CONSECUTIVE)
+                    .max(run_bonus[prev_row + j - 1]);
+                let consecutive = prev + SCORE_MATCH + bonus;
+                if consecutive >= score {
+                    score = consecutive;
+                    from[row + j] = j - 1;
+                    run_bonus[row + j] = run_bonus[prev_row + j - 1].max(bonuses[j]);
+                }
+            }
+
+            best[row + j] = score;
+        }
+    }
+
+    let last_row = (m - 1) * n;
+    let (mut j, score) = (0..n)
+        .map(|j| (j, best[last_row + j]))
+        .filter(|&(_, s)| s > unreachable)
+        .max_by_key(|&(j, s)| (s, std::cmp::Reverse(j)))?;
+
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = j;
+        j = from[i * n + j];
+    }
+
+    Some((score, positions))
+}
+
+// leftmost greedy match, used for very long texts
+fn greedy_match(window: &[char], pattern: &[char], bonuses: &[i32]) -> Option<(i32, Vec<usize>)> {
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut score = 0;
+    let mut pi = 0;
+    let mut last: Option<usize> = None;
+
+    for (j, &ch) in window.iter().enumerate() {
+        if pi == pattern.len() {
+            break;
+        }
+        if ch != pattern[pi] {
+            continue;
+        }
+
+        score += SCORE_MATCH;
+        match last {
+            Some(l) if l + 1 == j => score += bonuses[j].max(BONUS_CONSECUTIVE),
+            Some(l) => score += SCORE_GAP_START + SCORE_GAP_EXTENSION * (j - l - 2) as i32 + bonuses[j],
+            None => score += bonuses[j] * BONUS_FIRST_CHAR_MULTIPLIER,
+        }
+
+        positions.push(j);
+        last = Some(j);
+        pi += 1;
+    }
+
+    if pi < pattern.len() {
+        return None;
+    }
+
+    Some((score, positions))
+}
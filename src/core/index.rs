@@ -0,0 +1,204 @@
+// persistent comment index, stored in <directory>/.fuzc/index.json
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::engine::parse_file;
+use super::parallel;
+use super::parser::{CommentMatch, Language, PARSER_BACKEND};
+use super::source::SourceFile;
+
+pub const INDEX_DIR: &str = ".fuzc";
+const INDEX_FILE: &str = "index.json";
+const GITIGNORE: &str = "*\n!.gitignore\n!languages.toml\n";
+const OLD_GITIGNORE: &str = "*\n";
+
+// bump whenever an extractor's output changes so old indexes get rebuilt
+const INDEX_VERSION: u32 = 7;
+
+#[derive(Serialize, Deserialize)]
+pub struct CommentIndex {
+    version: u32,
+    parser: String, // PARSER_BACKEND, builds with and without tree-sitter extract different spans
+    files: HashMap<PathBuf, IndexEntry>, // keyed by path relative to `root`
+    #[serde(skip)]
+    root: PathBuf,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct IndexEntry {
+    language: String,
+    modified: u64,
+    size: u64,
+    hash: u64,
+    comments: Vec<CommentMatch>,
+}
+
+pub struct RefreshStats {
+    pub files: usize,
+    pub parsed: usize,
+    pub changed: bool, // whether the index differs from the one it was refreshed from
+}
+
+pub fn index_path(dir: &Path) -> PathBuf {
+    dir.join(INDEX_DIR).join(INDEX_FILE)
+}
+
+pub fn exists(dir: &Path) -> bool {
+    index_path(dir).is_file()
+}
+
+// an unreadable or outdated index is treated like a missing one
+pub fn load(dir: &Path) -> Option<CommentIndex> {
+    let data = fs::read(index_path(dir)).ok()?;
+    let mut index: CommentIndex = serde_json::from_slice(&data).ok()?;
+    if index.version != INDEX_VERSION || index.parser != PARSER_BACKEND {
+        return None;
+    }
+
+    index.root = dir.to_path_buf();
+    Some(index)
+}
+
+impl CommentIndex {
+    /// Builds an index for `files`, reusing entries from `previous` for files
+    /// that haven't changed and re-parsing the rest. With `keep_unseen`,
+    /// entries for files that weren't loaded this time are carried over;
+    /// without it they're dropped, unless they're in a language outside
+    /// `languages` (the --lang filter, empty for all languages).
+    pub fn refresh(
+        previous: Option<&CommentIndex>,
+        root: &Path,
+        files: &[SourceFile],
+        threads: usize,
+        keep_unseen: bool,
+        languages: &[Language],
+    ) -> (CommentIndex, RefreshStats) {
+        let entries = parallel::flat_map(files, threads, |file| {
+            let language = file.language;
+            let key = relative_key(root, &file.path);
+
+            if let Some(entry) = previous.and_then(|index| index.valid_entry(file, language)) {
+                let touched = entry.modified != file.modified;
+                let entry = IndexEntry { modified: file.modified, ..entry.clone() };
+                return Some((key, entry, false, touched));
+            }
+
+            let entry = IndexEntry {
+                language: language_key(language),
+                modified: file.modified,
+                size: file.size,
+                hash: content_hash(&file.content),
+                comments: parse_file(file),
+            };
+            Some((key, entry, true, true))
+        });
+
+        let mut stats = RefreshStats {
+            files: entries.len(),
+            parsed: 0,
+            changed: previous.is_none(),
+        };
+
+        let mut index = CommentIndex {
+            version: INDEX_VERSION,
+            parser: PARSER_BACKEND.to_string(),
+            files: HashMap::with_capacity(entries.len()),
+            root: root.to_path_buf(),
+        };
+
+        for (key, entry, parsed, changed) in entries {
+            stats.parsed += parsed as usize;
+            stats.changed |= changed;
+            index.files.insert(key, entry);
+        }
+
+        if let Some(previous) = previous {
+            for (key, entry) in &previous.files {
+                if index.files.contains_key(key) {
+                    continue;
+                }
+                // files of other languages weren't looked at, not deleted
+                let filtered_out = !languages.is_empty()
+                    && !languages.iter().any(|&language| language_key(language) == entry.language);
+                if keep_unseen || filtered_out {
+                    index.files.insert(key.clone(), entry.clone());
+                } else {
+                    stats.changed = true; // dropped an entry for a deleted or ignored file
+                }
+            }
+        }
+
+        (index, stats)
+    }
+
+    /// Cached comments for `file`, if the index has an up to date entry for it.
+    pub fn lookup(&self, file: &SourceFile, language: Language) -> Option<&[CommentMatch]> {
+        self.valid_entry(file, language).map(|entry| entry.comments.as_slice())
+    }
+
+    // an entry is valid if size and mtime match, or if only the mtime moved
+    // but the content is identical (e.g. after a checkout or `touch`)
+    fn valid_entry(&self, file: &SourceFile, language: Language) -> Option<&IndexEntry> {
+        let entry = self.files.get(&relative_key(&self.root, &file.path))?;
+
+        if entry.language != language_key(language) || entry.size != file.size {
+            return None;
+        }
+        if entry.modified != file.modified && entry.hash != content_hash(&file.content) {
+            return None;
+        }
+
+        Some(entry)
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let dir = self.root.join(INDEX_DIR);
+        fs::create_dir_all(&dir)?;
+
+        // keep the cache out of version control without touching the user's
+        // .gitignore, but not the project's language definitions. The plain `*`
+        // one of directories indexed before languages.toml existed is updated,
+        // any other content is the user's
+        let gitignore = dir.join(".gitignore");
+        match fs::read_to_string(&gitignore) {
+            Ok(existing) if existing != OLD_GITIGNORE => {},
+            _ => fs::write(gitignore, GITIGNORE)?,
+        }
+
+        // write to a temp file first so a concurrent search never sees half an index
+        let tmp_path = dir.join(format!("{}.{}.tmp", INDEX_FILE, std::process::id()));
+        let mut writer = BufWriter::new(fs::File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        drop(writer);
+
+        fs::rename(tmp_path, index_path(&self.root))
+    }
+}
+
+// language definitions from languages.toml can change without a new fuzc
+// version, so entries for those languages are also keyed by the definition
+fn language_key(language: Language) -> String {
+    match language {
+        Language::Custom(spec) => format!("{}:{:016x}", spec.name(), content_hash(&format!("{:?}", spec.definition))),
+        _ => language.name().to_string(),
+    }
+}
+
+fn relative_key(root: &Path, path: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_path_buf()
+}
+
+// FNV-1a, stable across runs and Rust versions unlike the std hasher
+fn content_hash(content: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in content.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
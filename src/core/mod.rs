@@ -1,6 +1,11 @@
 pub mod discover;
 pub mod source;
+pub mod notebook;
 pub mod engine;
 pub mod present;
 pub mod parser;
-pub mod search;
\ No newline at end of file
+pub mod search;
+pub mod tags;
+pub mod fuzzy;
+pub mod parallel;
+pub mod index;
\ No newline at end of file
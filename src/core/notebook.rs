@@ -0,0 +1,133 @@
+// Jupyter notebooks. The cells are joined into one text, code cells are parsed
+// on their own with the kernel language's extractor and markdown cells are
+// reported whole as doc comments
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::parser::{extract_comments_from_content, CommentMatch, CommentType, Language};
+use super::source::normalize_line_endings;
+
+pub struct Notebook {
+    pub content: String,
+    pub cells: Vec<Cell>,
+    pub language: Language,
+}
+
+#[derive(Clone, Copy)]
+pub struct Cell {
+    pub start: usize, // byte offset of the cell's first line in the joined text
+    pub kind: CellKind,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum CellKind {
+    Code,
+    Markdown,
+    Raw,
+}
+
+// the parts of the nbformat 4 schema we need
+#[derive(Deserialize)]
+struct NotebookJson {
+    cells: Vec<CellJson>,
+    #[serde(default)]
+    metadata: MetadataJson,
+}
+
+#[derive(Deserialize)]
+struct CellJson {
+    cell_type: String,
+    source: SourceJson,
+}
+
+// a cell's source is either one string or a list of lines
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SourceJson {
+    Text(String),
+    Lines(Vec<String>),
+}
+
+#[derive(Deserialize, Default)]
+struct MetadataJson {
+    kernelspec: Option<NameJson>,
+    language_info: Option<NameJson>,
+}
+
+#[derive(Deserialize)]
+struct NameJson {
+    language: Option<String>,
+    name: Option<String>,
+}
+
+pub fn is_notebook(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "ipynb")
+}
+
+// None if `json` isn't a notebook or its kernel's language isn't supported.
+// Notebooks without language metadata are taken to be Python
+pub fn parse(json: &str) -> Option<Notebook> {
+    let notebook: NotebookJson = serde_json::from_str(json).ok()?;
+    let metadata = notebook.metadata;
+    let language_name = metadata.kernelspec.and_then(|kernel| kernel.language)
+        .or_else(|| metadata.language_info.and_then(|info| info.name))
+        .unwrap_or_else(|| "python".to_string());
+    let language = Language::from_name(&language_name)?;
+
+    let mut content = String::new();
+    let mut cells = Vec::with_capacity(notebook.cells.len());
+    for cell in notebook.cells {
+        let kind = match cell.cell_type.as_str() {
+            "code" => CellKind::Code,
+            "markdown" => CellKind::Markdown,
+            _ => CellKind::Raw,
+        };
+        cells.push(Cell { start: content.len(), kind });
+
+        let source = match cell.source {
+            SourceJson::Text(text) => text,
+            SourceJson::Lines(lines) => lines.concat(),
+        };
+        content.push_str(&normalize_line_endings(&source));
+        // every cell starts on a line of its own
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+    }
+
+    Some(Notebook { content, cells, language })
+}
+
+pub fn extract_comments(content: &str, cells: &[Cell], language: Language) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+
+    for (i, cell) in cells.iter().enumerate() {
+        let end = cells.get(i + 1).map_or(content.len(), |next| next.start);
+        let source = &content[cell.start..end];
+
+        match cell.kind {
+            // a string or comment left open in one cell doesn't run into the next
+            CellKind::Code => {
+                comments.extend(extract_comments_from_content(source, language).into_iter().map(|comment| CommentMatch {
+                    start_byte: cell.start + comment.start_byte,
+                    end_byte: cell.start + comment.end_byte,
+                    ..comment
+                }));
+            },
+            CellKind::Markdown if !source.trim().is_empty() => {
+                let start = cell.start + (source.len() - source.trim_start().len());
+                let end = cell.start + source.trim_end().len();
+                comments.push(CommentMatch { start_byte: start, end_byte: end, comment_type: CommentType::Doc });
+            },
+            _ => {},
+        }
+    }
+
+    comments
+}
+
+// the 0-based index of the cell containing `byte`
+pub fn cell_at(cells: &[Cell], byte: usize) -> usize {
+    cells.partition_point(|cell| cell.start <= byte).saturating_sub(1)
+}
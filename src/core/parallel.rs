@@ -0,0 +1,55 @@
+// order-preserving parallel map used by the load/parse pipeline
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// small enough that one huge file doesn't stall a whole worker's share
+const CHUNK_SIZE: usize = 32;
+
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+/// Applies `f` to every item on up to `threads` worker threads and returns the
+/// flattened results in the same order as `items`.
+pub fn flat_map<'a, T, R, I, F>(items: &'a [T], threads: usize, f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    I: IntoIterator<Item = R>,
+    F: Fn(&'a T) -> I + Sync,
+{
+    let chunk_count = items.len().div_ceil(CHUNK_SIZE);
+    let threads = threads.clamp(1, chunk_count.max(1));
+
+    if threads == 1 {
+        return items.iter().flat_map(&f).collect();
+    }
+
+    // workers grab the next chunk index, results are slotted back by index
+    let next_chunk = AtomicUsize::new(0);
+    let slots: Vec<Mutex<Vec<R>>> = (0..chunk_count).map(|_| Mutex::new(Vec::new())).collect();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                loop {
+                    let chunk = next_chunk.fetch_add(1, Ordering::Relaxed);
+                    if chunk >= chunk_count {
+                        break;
+                    }
+
+                    let start = chunk * CHUNK_SIZE;
+                    let end = (start + CHUNK_SIZE).min(items.len());
+                    let results: Vec<R> = items[start..end].iter().flat_map(&f).collect();
+                    *slots[chunk].lock().unwrap() = results;
+                }
+            });
+        }
+    });
+
+    slots
+        .into_iter()
+        .flat_map(|slot| slot.into_inner().unwrap())
+        .collect()
+}
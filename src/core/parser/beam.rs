@@ -0,0 +1,159 @@
+// Erlang's `%` and Elixir's `#` comments. The strings given to Elixir's @doc,
+// @moduledoc and @typedoc and to Erlang's -doc and -moduledoc are docstrings
+use super::scan::{closing_bracket, delimited_end, interpolated_end, is_ident_char, line_end, next_line_start};
+use super::{CommentMatch, CommentType};
+
+pub fn extract_erlang_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        pos = match ch {
+            '%' => {
+                let end = line_end(content, pos);
+                comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+                end
+            },
+            // -doc "...". and -moduledoc("""...""").
+            '-' if content[..pos].ends_with('\n') || pos == 0 => {
+                let attribute = rest[1..].find(|c: char| !is_ident_char(c)).map_or(&rest[1..], |len| &rest[1..1 + len]);
+                let value = pos + 1 + attribute.len();
+                let value = value + content[value..].len() - content[value..].trim_start_matches([' ', '\t', '(']).len();
+
+                match erlang_string_end(content, value) {
+                    Some(end) if attribute == "doc" || attribute == "moduledoc" => {
+                        comments.push(CommentMatch { start_byte: value, end_byte: end, comment_type: CommentType::Docstring });
+                        end
+                    },
+                    _ => pos + 1,
+                }
+            },
+            '$' => {
+                // char literals: $a, $%, $\n
+                let escaped = usize::from(rest[1..].starts_with('\\'));
+                pos + 1 + escaped + rest[1 + escaped..].chars().next().map_or(0, char::len_utf8)
+            },
+            '\'' => delimited_end(content, pos + 1, '\''),
+            _ => erlang_string_end(content, pos).unwrap_or(pos + ch.len_utf8()),
+        };
+    }
+
+    comments
+}
+
+pub fn extract_elixir_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        pos = match ch {
+            '#' => {
+                let end = line_end(content, pos);
+                comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+                end
+            },
+            '@' => {
+                let attribute = rest[1..].find(|c: char| !is_ident_char(c)).map_or(&rest[1..], |len| &rest[1..1 + len]);
+                let value = pos + 1 + attribute.len();
+                let value = value + content[value..].len() - content[value..].trim_start_matches([' ', '\t']).len();
+
+                match elixir_string_end(content, value) {
+                    Some(end) if matches!(attribute, "doc" | "moduledoc" | "typedoc") => {
+                        comments.push(CommentMatch { start_byte: value, end_byte: end, comment_type: CommentType::Docstring });
+                        end
+                    },
+                    _ => value,
+                }
+            },
+            // char literals like ?a and ?#, but `valid?` is a name
+            '?' if !content[..pos].ends_with(is_ident_char) => {
+                let escaped = usize::from(rest[1..].starts_with('\\'));
+                pos + 1 + escaped + rest[1 + escaped..].chars().next().map_or(0, char::len_utf8)
+            },
+            _ if is_ident_char(ch) => pos + rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len()),
+            _ => elixir_string_end(content, pos).unwrap_or(pos + ch.len_utf8()),
+        };
+    }
+
+    comments
+}
+
+// the end of an Erlang string or ~sigil starting at `pos`, None if there's none
+fn erlang_string_end(content: &str, pos: usize) -> Option<usize> {
+    let rest = &content[pos..];
+    if rest.starts_with("\"\"\"") {
+        return Some(triple_quoted_end(content, pos));
+    }
+    if rest.starts_with('"') {
+        return Some(delimited_end(content, pos + 1, '"'));
+    }
+
+    // ~"...", ~b"...", ~S[...]
+    let sigil = rest.strip_prefix('~')?;
+    let modifier = usize::from(sigil.starts_with(['b', 'B', 's', 'S']));
+    let open = sigil[modifier..].chars().next()?;
+    let body = pos + 1 + modifier;
+    if content[body..].starts_with("\"\"\"") {
+        return Some(triple_quoted_end(content, body));
+    }
+    is_sigil_delimiter(open).then(|| delimited_end(content, body + 1, open))
+}
+
+// the end of an Elixir string, charlist or ~sigil starting at `pos`, None if
+// there's none. Lowercase sigils interpolate #{...}, uppercase ones don't
+fn elixir_string_end(content: &str, pos: usize) -> Option<usize> {
+    let rest = &content[pos..];
+    let quote = rest.chars().next()?;
+    if quote == '"' || quote == '\'' {
+        if rest.starts_with(&quote.to_string().repeat(3)) {
+            return Some(triple_quoted_end(content, pos));
+        }
+        return Some(interpolated_end(content, pos + 1, quote));
+    }
+
+    // ~r/.../i, ~s(...), ~S"""..."""; multi-letter sigils are uppercase
+    let name = rest.strip_prefix('~')?;
+    let name_len = name.find(|c: char| !c.is_ascii_alphanumeric()).unwrap_or(name.len());
+    let open = name[name_len..].chars().next()?;
+    let lowercase = name.starts_with(|c: char| c.is_ascii_lowercase());
+    if name_len == 0 || !is_sigil_delimiter(open) {
+        return None;
+    }
+
+    let body = pos + 1 + name_len;
+    let end = if content[body..].starts_with("\"\"\"") || content[body..].starts_with("'''") {
+        triple_quoted_end(content, body)
+    } else if lowercase {
+        interpolated_end(content, body + 1, open)
+    } else {
+        delimited_end(content, body + 1, open)
+    };
+    // modifiers, as in ~r/x/i
+    Some(end + content[end..].find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(content.len() - end))
+}
+
+fn is_sigil_delimiter(open: char) -> bool {
+    closing_bracket(open).is_some() || matches!(open, '/' | '|' | '"' | '\'')
+}
+
+// the end of a """ heredoc whose opening quotes are at `pos`: the body starts
+// on the next line and the closing quotes are alone on theirs
+fn triple_quoted_end(content: &str, pos: usize) -> usize {
+    let delimiter = &content[pos..pos + 3];
+    let mut line_start = next_line_start(content, pos);
+
+    while line_start < content.len() {
+        let line = &content[line_start..line_end(content, line_start)];
+        let indent = line.len() - line.trim_start().len();
+        if line[indent..].starts_with(delimiter) {
+            return line_start + indent + 3;
+        }
+        line_start = next_line_start(content, line_start);
+    }
+
+    content.len()
+}
@@ -0,0 +1,199 @@
+// C, C++ and Objective-C share one extractor: the C++-only syntax it knows
+// about (raw strings, digit separators) doesn't occur in valid C
+use super::scan::{is_ident_char, skip_to};
+use super::{CommentMatch, CommentType};
+
+enum CParseState {
+    Code,
+    LineComment,
+    BlockComment,
+    StringLiteral,
+    CharLiteral,
+}
+
+// longest delimiter the standard allows in R"delim(...)delim"
+const MAX_RAW_DELIMITER_LEN: usize = 16;
+
+pub fn extract_c_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = CParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    let mut prev_ch = '\n'; // last char seen in code
+    let mut in_number = false; // inside a numeric literal, where ' is a digit separator (1'000)
+    let mut at_line_start = true; // only whitespace so far on this line
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            CParseState::Code => {
+                match ch {
+                    '/' => {
+                        match chars.peek() {
+                            Some(&(_, '/')) => {
+                                comment_start = Some(byte_pos);
+                                state = CParseState::LineComment;
+                                chars.next();
+                            },
+                            Some(&(_, '*')) => {
+                                comment_start = Some(byte_pos);
+                                state = CParseState::BlockComment;
+                                chars.next();
+                            },
+                            _ => {}
+                        }
+                    },
+                    '"' => {
+                        if let Some(end) = raw_string_end(content, byte_pos, prev_ch) {
+                            skip_to(&mut chars, end);
+                        } else {
+                            state = CParseState::StringLiteral;
+                        }
+                    },
+                    '\'' if in_number && is_ident_char(prev_ch) => {}, // digit separator
+                    '\'' => { state = CParseState::CharLiteral },
+                    '#' if at_line_start => {
+                        if let Some(end) = disabled_block_end(content, byte_pos) {
+                            skip_to(&mut chars, end);
+                            prev_ch = '\n';
+                            at_line_start = true;
+                            continue;
+                        }
+                    },
+                    _ => {}
+                }
+
+                if is_ident_char(ch) && !is_ident_char(prev_ch) {
+                    in_number = ch.is_ascii_digit();
+                }
+                if ch == '\n' {
+                    at_line_start = true;
+                } else if !ch.is_whitespace() {
+                    at_line_start = false;
+                }
+                prev_ch = ch;
+            },
+            CParseState::LineComment => {
+                // a backslash at the end of the line continues the comment
+                if ch == '\n' && !content[..byte_pos].ends_with('\\') {
+                    comments.push(CommentMatch {
+                        start_byte: comment_start.take().unwrap(),
+                        end_byte: byte_pos,
+                        comment_type: CommentType::SingleLine,
+                    });
+                    state = CParseState::Code;
+                    prev_ch = '\n';
+                    at_line_start = true;
+                }
+            },
+            CParseState::BlockComment => {
+                if ch == '*'
+                    && let Some(&(end, '/')) = chars.peek()
+                {
+                    chars.next();
+                    comments.push(CommentMatch {
+                        start_byte: comment_start.take().unwrap(),
+                        end_byte: end + 1,
+                        comment_type: CommentType::MultiLine,
+                    });
+                    state = CParseState::Code;
+                    prev_ch = ' ';
+                }
+            },
+            CParseState::StringLiteral => {
+                match ch {
+                    '\\' => { chars.next(); },
+                    '"' | '\n' => {
+                        state = CParseState::Code;
+                        prev_ch = ch;
+                    },
+                    _ => {}
+                }
+            },
+            CParseState::CharLiteral => {
+                match ch {
+                    '\\' => { chars.next(); },
+                    '\'' | '\n' => {
+                        state = CParseState::Code;
+                        prev_ch = ch;
+                    },
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if let (CParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(CommentMatch {
+            start_byte: start,
+            end_byte: content.len(),
+            comment_type: CommentType::SingleLine,
+        });
+    }
+
+    comments
+}
+
+// for a `"` preceded by a raw string prefix (R, LR, uR, UR, u8R), the byte
+// position just past the closing `)delim"`
+fn raw_string_end(content: &str, quote_pos: usize, prev_ch: char) -> Option<usize> {
+    if prev_ch != 'R' {
+        return None;
+    }
+
+    // the prefix must be a whole token, otherwise it's e.g. a macro FOOR"..."
+    let before = content[..quote_pos - 1].trim_end_matches(['L', 'u', 'U', '8']);
+    if before.chars().next_back().is_some_and(is_ident_char) {
+        return None;
+    }
+
+    let rest = &content[quote_pos + 1..];
+    let open = rest.find('(')?;
+    let delimiter = &rest[..open];
+    if delimiter.len() > MAX_RAW_DELIMITER_LEN
+        || delimiter.contains(|c: char| c.is_whitespace() || c == ')' || c == '\\')
+    {
+        return None;
+    }
+
+    let terminator = format!("){}\"", delimiter);
+    let body_start = quote_pos + 1 + open + 1;
+    let end = content[body_start..].find(&terminator)?;
+    Some(body_start + end + terminator.len())
+}
+
+// for a `#if 0` directive, the byte position where live code resumes: the
+// start of the matching #else/#elif, or the end of the matching #endif line
+fn disabled_block_end(content: &str, hash_pos: usize) -> Option<usize> {
+    let line_end = content[hash_pos..].find('\n').map_or(content.len(), |i| hash_pos + i);
+    let (directive, argument) = parse_directive(&content[hash_pos..line_end])?;
+    if directive != "if" || argument.split("//").next()?.split("/*").next()?.trim() != "0" {
+        return None;
+    }
+
+    let mut depth = 0;
+    let mut pos = line_end + 1;
+    while pos < content.len() {
+        let line_end = content[pos..].find('\n').map_or(content.len(), |i| pos + i);
+        if let Some((directive, _)) = parse_directive(&content[pos..line_end]) {
+            match directive {
+                "if" | "ifdef" | "ifndef" => depth += 1,
+                "else" | "elif" | "elifdef" | "elifndef" if depth == 0 => return Some(pos),
+                "endif" if depth == 0 => return Some((line_end + 1).min(content.len())),
+                "endif" => depth -= 1,
+                _ => {}
+            }
+        }
+        pos = line_end + 1;
+    }
+
+    // unterminated: everything after #if 0 is disabled
+    Some(content.len())
+}
+
+// "  #  if 0" -> ("if", "0")
+fn parse_directive(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix('#')?.trim_start();
+    let name_len = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+    Some((&rest[..name_len], &rest[name_len..]))
+}
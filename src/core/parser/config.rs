@@ -0,0 +1,233 @@
+// line-oriented formats with `#` comments: YAML, TOML, Makefiles and Dockerfiles
+use super::scan::{heredoc_end, line_end};
+use super::shell::parse_heredoc;
+use super::{CommentMatch, CommentType};
+
+pub fn extract_yaml_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut quote: Option<char> = None; // open quoted scalar, these may span lines
+    let mut block_indent: Option<usize> = None; // indentation of the line that opened a `|` or `>` block scalar
+    let mut line_start = 0;
+
+    while line_start < content.len() {
+        let end = line_end(content, line_start);
+        let line = &content[line_start..end];
+        let indent = line.len() - line.trim_start_matches(' ').len();
+
+        // block scalar content is everything indented deeper than the line that opened it
+        if let Some(parent) = block_indent {
+            if line.trim().is_empty() || indent > parent {
+                line_start = end + 1;
+                continue;
+            }
+            block_indent = None;
+        }
+
+        let mut code_end = line.len();
+        let mut prev_ch = ' ';
+        let mut chars = line.char_indices().peekable();
+        while let Some((i, ch)) = chars.next() {
+            match quote {
+                // '' is an escaped quote in single-quoted scalars
+                Some('\'') if ch == '\'' && chars.next_if(|&(_, next)| next == '\'').is_none() => quote = None,
+                Some('"') if ch == '\\' => { chars.next(); },
+                Some('"') if ch == '"' => quote = None,
+                Some(_) => {},
+                None => match ch {
+                    '#' if prev_ch.is_whitespace() => {
+                        comments.push(CommentMatch {
+                            start_byte: line_start + i,
+                            end_byte: end,
+                            comment_type: CommentType::SingleLine,
+                        });
+                        code_end = i;
+                        break;
+                    },
+                    // quotes only count at the start of a scalar, not in `key: it's`
+                    '\'' | '"' => {
+                        let before = line[..i].trim_end();
+                        if before.is_empty() || before.ends_with([':', '-', '[', '{', ',', '?']) {
+                            quote = Some(ch);
+                        }
+                    },
+                    _ => {}
+                },
+            }
+            prev_ch = ch;
+        }
+
+        if quote.is_none() && opens_block_scalar(&line[..code_end]) {
+            block_indent = Some(indent);
+        }
+        line_start = end + 1;
+    }
+
+    comments
+}
+
+// `key: |`, `- >-`, `key: |2+`
+fn opens_block_scalar(code: &str) -> bool {
+    let code = code.trim_end().trim_end_matches(|c: char| c == '+' || c == '-' || c.is_ascii_digit());
+    code.strip_suffix(['|', '>'])
+        .is_some_and(|before| before.is_empty() || before.ends_with(char::is_whitespace))
+}
+
+pub fn extract_toml_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = content[pos..].find(['#', '"', '\'']) {
+        let start = pos + offset;
+        pos = match content[start..].chars().next() {
+            Some('#') => {
+                let end = line_end(content, start);
+                comments.push(CommentMatch {
+                    start_byte: start,
+                    end_byte: end,
+                    comment_type: CommentType::SingleLine,
+                });
+                end
+            },
+            Some(quote) => toml_string_end(content, start, quote),
+            None => break,
+        };
+    }
+
+    comments
+}
+
+// the end of a basic ("..."), literal ('...') or multi-line ("""...""", '''...''')
+// string starting at `pos`. Single-line strings can't span lines
+fn toml_string_end(content: &str, pos: usize, quote: char) -> usize {
+    let triple = if quote == '"' { "\"\"\"" } else { "'''" };
+
+    if content[pos..].starts_with(triple) {
+        let mut chars = content[pos + 3..].char_indices();
+        while let Some((i, ch)) = chars.next() {
+            if ch == '\\' && quote == '"' {
+                chars.next();
+            } else if content[pos + 3 + i..].starts_with(triple) {
+                // up to two more quotes may belong to the string: """a "quote"""""
+                let rest = &content[pos + 3 + i..];
+                let quotes = rest.len() - rest.trim_start_matches(quote).len();
+                return pos + 3 + i + quotes.min(5);
+            }
+        }
+        return content.len();
+    }
+
+    let end = line_end(content, pos);
+    let mut chars = content[pos + 1..end].char_indices();
+    while let Some((i, ch)) = chars.next() {
+        if ch == '\\' && quote == '"' {
+            chars.next();
+        } else if ch == quote {
+            return pos + 1 + i + 1;
+        }
+    }
+    end
+}
+
+pub fn extract_makefile_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut in_define = false; // define ... endef bodies are kept verbatim
+    let mut line_start = 0;
+
+    while line_start < content.len() {
+        let end = line_end(content, line_start);
+        let line = &content[line_start..end];
+        let trimmed = line.trim_start();
+
+        if in_define {
+            in_define = trimmed.trim_end() != "endef";
+            line_start = end + 1;
+            continue;
+        }
+        if trimmed.strip_prefix("define").is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace)) {
+            in_define = true;
+            line_start = end + 1;
+            continue;
+        }
+
+        // recipe lines go to the shell, only whole-line comments are comments there
+        let hash = if line.starts_with('\t') {
+            trimmed.starts_with('#').then(|| line.len() - trimmed.len())
+        } else {
+            unescaped_hash(line)
+        };
+
+        if let Some(offset) = hash {
+            // a backslash at the end of the line continues the comment
+            let mut comment_end = end;
+            while content[..comment_end].ends_with('\\') && comment_end < content.len() {
+                comment_end = line_end(content, comment_end + 1);
+            }
+            comments.push(CommentMatch {
+                start_byte: line_start + offset,
+                end_byte: comment_end,
+                comment_type: CommentType::SingleLine,
+            });
+            line_start = comment_end + 1;
+        } else {
+            line_start = end + 1;
+        }
+    }
+
+    comments
+}
+
+// `\#` is a literal `#` in make
+fn unescaped_hash(line: &str) -> Option<usize> {
+    let mut chars = line.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => { chars.next(); },
+            '#' => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+pub fn extract_dockerfile_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut in_header = true; // parser directives are only recognized before anything else
+    let mut line_start = 0;
+
+    while line_start < content.len() {
+        let end = line_end(content, line_start);
+        let line = &content[line_start..end];
+        let trimmed = line.trim_start();
+
+        // `#` only starts a comment at the beginning of a line, elsewhere it's an argument
+        if trimmed.starts_with('#') {
+            in_header = in_header && is_parser_directive(trimmed);
+            comments.push(CommentMatch {
+                start_byte: line_start + line.len() - trimmed.len(),
+                end_byte: end,
+                comment_type: if in_header { CommentType::Directive } else { CommentType::SingleLine },
+            });
+            line_start = end + 1;
+            continue;
+        }
+        in_header = false;
+
+        // RUN <<EOF ... EOF: heredoc bodies start on the next line
+        let mut next_line = end + 1;
+        for (i, _) in line.match_indices("<<") {
+            if let Some(((delimiter, indented), _)) = parse_heredoc(content, line_start + i + 2) {
+                next_line = heredoc_end(content, next_line.min(content.len()), delimiter, indented);
+            }
+        }
+        line_start = next_line;
+    }
+
+    comments
+}
+
+// # syntax=docker/dockerfile:1, # escape=`, # check=skip=...
+fn is_parser_directive(comment: &str) -> bool {
+    comment[1..].split_once('=').is_some_and(|(name, _)| {
+        matches!(name.trim().to_ascii_lowercase().as_str(), "syntax" | "escape" | "check")
+    })
+}
@@ -0,0 +1,95 @@
+use super::scan::skip_to;
+use super::{CommentMatch, CommentType};
+
+enum CssParseState {
+    Code,
+    LineComment,
+    BlockComment,
+    StringLiteral(char), // the quote that closes it, `content: "/*"` isn't a comment
+}
+
+pub fn extract_css_comments(content: &str) -> Vec<CommentMatch> {
+    extract_stylesheet_comments(content, false)
+}
+
+// SCSS and Less also have `//` line comments
+pub fn extract_scss_comments(content: &str) -> Vec<CommentMatch> {
+    extract_stylesheet_comments(content, true)
+}
+
+fn extract_stylesheet_comments(content: &str, line_comments: bool) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = CssParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            CssParseState::Code => {
+                match ch {
+                    '/' if chars.next_if(|&(_, next)| next == '*').is_some() => {
+                        comment_start = Some(byte_pos);
+                        state = CssParseState::BlockComment;
+                    },
+                    '/' if line_comments && chars.next_if(|&(_, next)| next == '/').is_some() => {
+                        comment_start = Some(byte_pos);
+                        state = CssParseState::LineComment;
+                    },
+                    '"' | '\'' => { state = CssParseState::StringLiteral(ch) },
+                    '\\' => { chars.next(); },
+                    // unquoted url(http://...) isn't a comment either
+                    '(' if content.as_bytes()[byte_pos.saturating_sub(3)..byte_pos].eq_ignore_ascii_case(b"url") => {
+                        let body = &content[byte_pos + 1..];
+                        if !body.trim_start().starts_with(['"', '\'']) {
+                            skip_to(&mut chars, body.find(')').map_or(content.len(), |i| byte_pos + 1 + i + 1));
+                        }
+                    },
+                    _ => {}
+                }
+            },
+            CssParseState::LineComment => {
+                if ch == '\n' {
+                    comments.push(CommentMatch {
+                        start_byte: comment_start.take().unwrap(),
+                        end_byte: byte_pos,
+                        comment_type: CommentType::SingleLine,
+                    });
+                    state = CssParseState::Code;
+                }
+            },
+            CssParseState::BlockComment => {
+                if ch == '*'
+                    && let Some(&(end, '/')) = chars.peek()
+                {
+                    chars.next();
+                    comments.push(CommentMatch {
+                        start_byte: comment_start.take().unwrap(),
+                        end_byte: end + 1,
+                        comment_type: CommentType::MultiLine,
+                    });
+                    state = CssParseState::Code;
+                }
+            },
+            CssParseState::StringLiteral(quote) => {
+                match ch {
+                    '\\' => { chars.next(); },
+                    // an unescaped newline ends a bad string
+                    '\n' => { state = CssParseState::Code },
+                    _ if ch == quote => { state = CssParseState::Code },
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if let (CssParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(CommentMatch {
+            start_byte: start,
+            end_byte: content.len(),
+            comment_type: CommentType::SingleLine,
+        });
+    }
+
+    comments
+}
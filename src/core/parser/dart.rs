@@ -0,0 +1,89 @@
+// `//` and `///` comments and nesting `/* */` blocks. Strings use either quote,
+// may be '''triple-quoted''' and hold ${...} interpolations unless they're r'raw'
+use super::scan::{hash_comment, is_ident_char, line_end, nested_comment_end};
+use super::{CommentMatch, CommentType};
+
+pub fn extract_dart_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    // #!/usr/bin/env dart
+    if content.starts_with("#!") {
+        pos = line_end(content, 0);
+        comments.push(hash_comment(content, 0, pos));
+    }
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        pos = if rest.starts_with("//") {
+            let end = line_end(content, pos);
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+            end
+        } else if rest.starts_with("/*") {
+            let end = nested_comment_end(content, pos, "/*", "*/");
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::MultiLine });
+            end
+        } else if ch == '"' || ch == '\'' {
+            string_end(content, pos, false)
+        } else if ch == 'r' && rest[1..].starts_with(['"', '\'']) && !content[..pos].ends_with(is_ident_char) {
+            string_end(content, pos + 1, true)
+        } else if is_ident_char(ch) {
+            pos + rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len())
+        } else {
+            pos + ch.len_utf8()
+        };
+    }
+
+    comments
+}
+
+// the end of the string whose opening quote is at `start`; only triple-quoted
+// strings span lines
+fn string_end(content: &str, start: usize, raw: bool) -> usize {
+    let quote = if content[start..].starts_with('"') { "\"" } else { "'" };
+    let triple = quote.repeat(3);
+    let delimiter = if content[start..].starts_with(&triple) { triple.as_str() } else { quote };
+    let mut pos = start + delimiter.len();
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+        if rest.starts_with(delimiter) {
+            return pos + delimiter.len();
+        }
+
+        pos = match ch {
+            '\\' if !raw => pos + 1 + rest[1..].chars().next().map_or(0, char::len_utf8),
+            '$' if !raw && rest.starts_with("${") => interpolation_end(content, pos + 2),
+            '\n' if delimiter.len() == 1 => return pos,
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    content.len()
+}
+
+// the end of a ${...} interpolation whose body starts at `pos`
+fn interpolation_end(content: &str, mut pos: usize) -> usize {
+    let mut depth = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+        pos = match ch {
+            '}' if depth == 0 => return pos + 1,
+            '}' => {
+                depth -= 1;
+                pos + 1
+            },
+            '{' => {
+                depth += 1;
+                pos + 1
+            },
+            '"' | '\'' => string_end(content, pos, false),
+            'r' if rest[1..].starts_with(['"', '\'']) && !content[..pos].ends_with(is_ident_char) => string_end(content, pos + 1, true),
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    content.len()
+}
@@ -0,0 +1,34 @@
+// doc comments that are written like ordinary ones: Javadoc-style `/** */`
+// blocks and the `///` and `//!` lines of Rust, Doxygen and the .NET languages.
+// The extractors report them as plain comments and they're reclassified here
+use super::{CommentMatch, CommentType, Language};
+
+pub fn classify_doc_comments(content: &str, language: Language, comments: &mut [CommentMatch]) {
+    for comment in comments {
+        if matches!(comment.comment_type, CommentType::SingleLine | CommentType::MultiLine)
+            && let Some(comment_type) = doc_comment_type(&content[comment.start_byte..comment.end_byte], language)
+        {
+            comment.comment_type = comment_type;
+        }
+    }
+}
+
+fn doc_comment_type(text: &str, language: Language) -> Option<CommentType> {
+    // /**/ is empty and /*** a banner
+    let block_doc = text.starts_with("/**") && !text.starts_with("/**/") && !text.starts_with("/***");
+    let line_doc = text.starts_with("///") && !text.starts_with("////");
+    // Rust's inner docs and Doxygen's Qt style
+    let inner_doc = text.starts_with("//!") || text.starts_with("/*!");
+
+    let is_doc = match language {
+        Language::Rust | Language::C | Language::Cpp | Language::ObjectiveC => block_doc || line_doc || inner_doc,
+        Language::CSharp | Language::Swift | Language::Dart => block_doc || line_doc,
+        Language::FSharp => line_doc,
+        // TypeScript's /// <reference ... /> lines are compiler directives
+        Language::JavaScript | Language::TypeScript if text.starts_with("/// <") => return Some(CommentType::Directive),
+        Language::Java | Language::JavaScript | Language::TypeScript | Language::Kotlin | Language::Scala
+        | Language::Groovy | Language::Php | Language::Html | Language::Vue | Language::Svelte => block_doc,
+        _ => false,
+    };
+    is_doc.then_some(CommentType::Doc)
+}
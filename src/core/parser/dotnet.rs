@@ -0,0 +1,128 @@
+// C# and F#: `//` comments, including `///` XML doc comments, plus C#'s
+// `/* */` and F#'s nesting `(* *)`. Both share the verbatim @"", raw """ and
+// interpolated $"{x}" string forms
+use super::scan::{char_literal_end, is_ident_char, line_end, nested_comment_end};
+use super::{CommentMatch, CommentType};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Dialect {
+    CSharp,
+    FSharp,
+}
+
+pub fn extract_csharp_comments(content: &str) -> Vec<CommentMatch> {
+    extract_dotnet_comments(content, Dialect::CSharp)
+}
+
+pub fn extract_fsharp_comments(content: &str) -> Vec<CommentMatch> {
+    extract_dotnet_comments(content, Dialect::FSharp)
+}
+
+fn extract_dotnet_comments(content: &str, dialect: Dialect) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        if rest.starts_with("//") {
+            let end = line_end(content, pos);
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+            pos = end;
+            continue;
+        }
+        let block_end = match dialect {
+            Dialect::CSharp => rest.strip_prefix("/*").map(|body| body.find("*/").map_or(content.len(), |i| pos + 2 + i + 2)),
+            // (*) is the multiplication operator
+            Dialect::FSharp if rest.starts_with("(*") && !rest.starts_with("(*)") => Some(nested_comment_end(content, pos, "(*", "*)")),
+            Dialect::FSharp => None,
+        };
+        if let Some(end) = block_end {
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::MultiLine });
+            pos = end;
+            continue;
+        }
+
+        pos = match ch {
+            '"' | '@' | '$' => string_end(content, pos, dialect).unwrap_or(pos + 1),
+            // F# type parameters like 'T aren't char literals
+            '\'' => char_literal_end(content, pos).unwrap_or(pos + 1),
+            // F# identifiers may end in primes: x'
+            _ if is_ident_char(ch) => {
+                let is_name_char = |c: char| is_ident_char(c) || (dialect == Dialect::FSharp && c == '\'');
+                pos + rest.find(|c: char| !is_name_char(c)).unwrap_or(rest.len())
+            },
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    comments
+}
+
+// the end of a "regular", @"verbatim" (with "" escapes) or """raw""" string,
+// any of them $"interpolated {like} this". `start` is at the first `$`, `@` or
+// `"`; None if there's no string there, as in C#'s @class identifiers. Only
+// F# allows line breaks in regular strings
+fn string_end(content: &str, start: usize, dialect: Dialect) -> Option<usize> {
+    let prefix_len = content[start..].find(|c: char| c != '$' && c != '@').unwrap_or(content.len() - start);
+    let prefix = &content[start..start + prefix_len];
+    let quote_start = start + prefix_len;
+    if !content[quote_start..].starts_with('"') || prefix.matches('@').count() > 1 {
+        return None;
+    }
+
+    let interpolated = prefix.contains('$');
+    let verbatim = prefix.contains('@');
+    let quotes = content[quote_start..].len() - content[quote_start..].trim_start_matches('"').len();
+
+    if !verbatim && quotes >= 3 {
+        // closed by as many quotes as opened it; holes can't contain that many
+        let closing = &content[quote_start..quote_start + quotes];
+        let body = quote_start + quotes;
+        return Some(content[body..].find(closing).map_or(content.len(), |i| body + i + quotes));
+    }
+    if !verbatim && quotes == 2 {
+        return Some(quote_start + 2);
+    }
+
+    let mut pos = quote_start + 1;
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+        pos = match ch {
+            '"' if verbatim && rest.starts_with("\"\"") => pos + 2,
+            '"' => return Some(pos + 1),
+            '\\' if !verbatim => pos + 1 + rest[1..].chars().next().map_or(0, char::len_utf8),
+            '\n' if !verbatim && dialect == Dialect::CSharp => return Some(pos),
+            '{' if interpolated && rest.starts_with("{{") => pos + 2,
+            '{' if interpolated => hole_end(content, pos + 1, dialect),
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    Some(content.len())
+}
+
+// the end of an interpolation hole whose body starts at `pos`; strings in it
+// may contain braces and quotes of their own
+fn hole_end(content: &str, mut pos: usize, dialect: Dialect) -> usize {
+    let mut depth = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        pos = match ch {
+            '}' if depth == 0 => return pos + 1,
+            '}' => {
+                depth -= 1;
+                pos + 1
+            },
+            '{' => {
+                depth += 1;
+                pos + 1
+            },
+            '"' | '@' | '$' => string_end(content, pos, dialect).unwrap_or(pos + 1),
+            '\'' => char_literal_end(content, pos).unwrap_or(pos + 1),
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    content.len()
+}
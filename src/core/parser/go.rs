@@ -0,0 +1,126 @@
+use super::{CommentMatch, CommentType};
+
+enum GoParseState {
+    Code,
+    LineComment,
+    BlockComment,
+    StringLiteral,
+    RawString, // `...`, no escapes and may span lines
+    RuneLiteral,
+}
+
+pub fn extract_go_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = GoParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            GoParseState::Code => {
+                match ch {
+                    '/' => {
+                        match chars.peek() {
+                            Some(&(_, '/')) => {
+                                comment_start = Some(byte_pos);
+                                state = GoParseState::LineComment;
+                                chars.next();
+                            },
+                            Some(&(_, '*')) => {
+                                comment_start = Some(byte_pos);
+                                state = GoParseState::BlockComment;
+                                chars.next();
+                            },
+                            _ => {}
+                        }
+                    },
+                    '"' => { state = GoParseState::StringLiteral },
+                    '`' => { state = GoParseState::RawString },
+                    '\'' => { state = GoParseState::RuneLiteral },
+                    _ => {}
+                }
+            },
+            GoParseState::LineComment => {
+                if ch == '\n' {
+                    let start = comment_start.take().unwrap();
+                    comments.push(line_comment(content, start, byte_pos));
+                    state = GoParseState::Code;
+                }
+            },
+            GoParseState::BlockComment => {
+                if ch == '*'
+                    && let Some(&(end, '/')) = chars.peek()
+                {
+                    chars.next();
+                    comments.push(CommentMatch {
+                        start_byte: comment_start.take().unwrap(),
+                        end_byte: end + 1,
+                        comment_type: CommentType::MultiLine,
+                    });
+                    state = GoParseState::Code;
+                }
+            },
+            GoParseState::StringLiteral => {
+                match ch {
+                    '\\' => { chars.next(); },
+                    '"' | '\n' => { state = GoParseState::Code },
+                    _ => {}
+                }
+            },
+            GoParseState::RawString => {
+                if ch == '`' {
+                    state = GoParseState::Code;
+                }
+            },
+            GoParseState::RuneLiteral => {
+                match ch {
+                    '\\' => { chars.next(); },
+                    '\'' | '\n' => { state = GoParseState::Code },
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if let (GoParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(line_comment(content, start, content.len()));
+    }
+
+    comments
+}
+
+fn line_comment(content: &str, start: usize, end: usize) -> CommentMatch {
+    let comment_type = if is_directive(&content[start..end]) {
+        CommentType::Directive
+    } else {
+        CommentType::SingleLine
+    };
+
+    CommentMatch { start_byte: start, end_byte: end, comment_type }
+}
+
+// tool directives rather than prose: //go:generate, //go:build, //lint:ignore
+// (any `//name:` without a space), //nolint, //export, //line and // +build
+pub fn is_directive(comment: &str) -> bool {
+    let body = &comment[2..];
+
+    if body.starts_with(" +build ") {
+        return true;
+    }
+
+    let name_len = body
+        .find(|c: char| !(c.is_ascii_lowercase() || c.is_ascii_digit()))
+        .unwrap_or(body.len());
+    let (name, rest) = body.split_at(name_len);
+    if name.is_empty() {
+        return false;
+    }
+
+    if rest.starts_with(':') && rest[1..].starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return true;
+    }
+
+    matches!(name, "nolint" | "export" | "line" | "extern")
+        && (rest.is_empty() || rest.starts_with([' ', ':', '\t']))
+}
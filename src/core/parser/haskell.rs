@@ -0,0 +1,90 @@
+// Haskell and Elm: `--` line comments and nesting `{- -}` blocks. Haddock's
+// `-- |`, `-- ^` and `{-| -}` are doc comments, `{-# ... #-}` pragmas directives
+use super::scan::{char_literal_end, is_ident_char, line_end, nested_comment_end};
+use super::{CommentMatch, CommentType};
+
+// chars that make up operators; `-->` or `|--` is an operator, not a comment
+const SYMBOL_CHARS: &str = "!#$%&*+./<=>?@\\^|-~:";
+
+pub fn extract_haskell_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments: Vec<CommentMatch> = Vec::new();
+    let mut pos = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        pos = if rest.starts_with("{-") {
+            let end = nested_comment_end(content, pos, "{-", "-}");
+            let comment_type = if rest.starts_with("{-#") {
+                CommentType::Directive
+            } else if rest.starts_with("{-|") || rest.starts_with("{-^") {
+                CommentType::Doc
+            } else {
+                CommentType::MultiLine
+            };
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type });
+            end
+        } else if rest.starts_with("--") && is_line_comment(content, pos) {
+            let end = line_end(content, pos);
+            let text = rest.trim_start_matches('-').trim_start();
+            // a Haddock comment continues on the `--` lines right below it
+            let continues_doc = comments.last().is_some_and(|last| {
+                last.comment_type == CommentType::Doc
+                    && content[last.end_byte..pos].trim_matches([' ', '\t']) == "\n"
+                    && content[last.start_byte..].starts_with("--")
+            });
+            let comment_type = if text.starts_with(['|', '^']) || continues_doc {
+                CommentType::Doc
+            } else {
+                CommentType::SingleLine
+            };
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type });
+            end
+        } else if ch == '"' {
+            string_end(content, pos)
+        } else if ch == '\'' {
+            char_literal_end(content, pos).unwrap_or(pos + 1)
+        } else if is_ident_char(ch) {
+            // names may end in primes: x'
+            pos + rest.find(|c: char| !is_ident_char(c) && c != '\'').unwrap_or(rest.len())
+        } else {
+            pos + ch.len_utf8()
+        };
+    }
+
+    comments
+}
+
+// two or more dashes that aren't part of a longer operator
+fn is_line_comment(content: &str, pos: usize) -> bool {
+    let after = content[pos..].trim_start_matches('-');
+    let symbol_before = content[..pos].ends_with(|c: char| SYMBOL_CHARS.contains(c));
+    let symbol_after = after.starts_with(|c: char| SYMBOL_CHARS.contains(c));
+    !symbol_before && !symbol_after
+}
+
+// the end of the string whose opening quote is at `start`. A `\` followed by
+// whitespace starts a gap that ends at the next `\`, which is how strings span
+// lines; GHC's """multiline strings""" do so directly
+fn string_end(content: &str, start: usize) -> usize {
+    if let Some(body) = content[start..].strip_prefix("\"\"\"") {
+        return body.find("\"\"\"").map_or(content.len(), |i| start + 3 + i + 3);
+    }
+
+    let mut pos = start + 1;
+    while let Some(ch) = content[pos..].chars().next() {
+        let next = pos + 1;
+        pos = match ch {
+            '"' => return pos + 1,
+            '\n' => return pos,
+            '\\' => match content[next..].chars().next() {
+                Some(c) if c.is_whitespace() => content[next..].find('\\').map_or(content.len(), |i| next + i + 1),
+                Some(c) => next + c.len_utf8(),
+                None => next,
+            },
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    content.len()
+}
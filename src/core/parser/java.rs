@@ -0,0 +1,88 @@
+use super::{CommentMatch, CommentType};
+
+enum JavaParseState {
+    Code,
+    SingleLineComment,
+    MultiLineComment,
+    StringLiteral,
+    CharLiteral
+}
+
+pub fn extract_java_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = JavaParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            JavaParseState::Code => {
+                match ch {
+                    '/' => {
+                        if let Some(&(_, next_ch)) = chars.peek() {
+                            match next_ch {
+                                '/' => {
+                                    comment_start = Some(byte_pos);
+                                    state = JavaParseState::SingleLineComment;
+                                    chars.next();
+                                },
+                                '*' => {
+                                    comment_start = Some(byte_pos);
+                                    state = JavaParseState::MultiLineComment;
+                                    chars.next();
+                                },
+                                _ => { continue }
+                            }
+                        }
+                    },
+                    '"' => { state = JavaParseState::StringLiteral },
+                    '\'' => { state = JavaParseState::CharLiteral },
+                    _ => { continue }
+                }
+            },
+            JavaParseState::SingleLineComment => {
+                if ch == '\n' {
+                    let comment_match = CommentMatch {
+                        start_byte: comment_start.unwrap(),
+                        end_byte: byte_pos, // the curr pos after moving to new line
+                        comment_type: CommentType::SingleLine,
+                    };
+
+                    comments.push(comment_match);
+                    state = JavaParseState::Code;
+                }
+            },
+            JavaParseState::MultiLineComment => {
+                if ch == '*'
+                    && let Some(&(_, '/')) = chars.peek()
+                {
+                    let comment_match = CommentMatch {
+                        start_byte: comment_start.unwrap(),
+                        end_byte: byte_pos + 2, // past the closing */
+                        comment_type: CommentType::MultiLine,
+                    };
+
+                    comments.push(comment_match);
+                    state = JavaParseState::Code;
+                    chars.next();
+                }
+            },
+            JavaParseState::StringLiteral => {
+                match ch {
+                    '\\' => { chars.next(); },
+                    '"' => { state = JavaParseState::Code; },
+                    _ => { /* do nothing (keep scanning) */ }
+                }
+            },
+            JavaParseState::CharLiteral => {
+                match ch {
+                    '\\' => { chars.next(); },
+                    '\'' => { state = JavaParseState::Code; },
+                    _ => { /* do nothing (keep scanning) */}
+                }
+            }
+        }
+    }
+    comments
+}
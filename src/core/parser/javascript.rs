@@ -0,0 +1,292 @@
+// JavaScript and TypeScript, JSX included. Strings, template literals, regex
+// literals and JSX text are skipped so the `//` and quotes in them aren't misread
+use super::scan::{hash_comment, is_ident_char, line_end};
+use super::{CommentMatch, CommentType};
+
+// keywords after which `/` starts a regex and `<` a JSX element rather than an operator
+const EXPRESSION_KEYWORDS: &[&str] = &[
+    "return", "typeof", "instanceof", "in", "of", "new", "delete", "void", "throw",
+    "case", "do", "else", "yield", "await",
+];
+
+pub fn extract_javascript_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    // #!/usr/bin/env node
+    if content.starts_with("#!") {
+        pos = line_end(content, 0);
+        comments.push(hash_comment(content, 0, pos));
+    }
+
+    scan_code(content, pos, false, &mut comments);
+    comments
+}
+
+// scans code starting at `pos`. With `in_braces` the code is a `${}` or JSX `{}`
+// expression, and the scan ends after its closing `}`; None if the file ends first
+fn scan_code(content: &str, mut pos: usize, in_braces: bool, comments: &mut Vec<CommentMatch>) -> Option<usize> {
+    let mut depth: usize = 0; // braces opened since `pos`
+    let mut expression_start = true; // no value right before, so `/` starts a regex
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        if let Some(comment) = comment_at(content, pos) {
+            pos = comment.end_byte;
+            comments.push(comment);
+            continue;
+        }
+
+        match ch {
+            '"' | '\'' => {
+                pos = string_end(content, pos + 1, ch);
+                expression_start = false;
+            },
+            '`' => {
+                pos = template_end(content, pos + 1, comments)?;
+                expression_start = false;
+            },
+            '/' if expression_start => {
+                // only single-line regexes, so a misread division can't swallow the file
+                match regex_end(content, pos + 1) {
+                    Some(end) => {
+                        pos = end;
+                        expression_start = false;
+                    },
+                    None => pos += 1,
+                }
+            },
+            '<' if expression_start => {
+                // TypeScript's `<T>value` casts look the same, they fail to parse as JSX
+                match jsx_element_end(content, pos, comments) {
+                    Some(end) => {
+                        pos = end;
+                        expression_start = false;
+                    },
+                    None => pos += 1,
+                }
+            },
+            '{' => {
+                depth += 1;
+                expression_start = true;
+                pos += 1;
+            },
+            '}' => {
+                if depth == 0 && in_braces {
+                    return Some(pos + 1);
+                }
+                depth = depth.saturating_sub(1);
+                expression_start = false;
+                pos += 1;
+            },
+            ')' | ']' => {
+                expression_start = false;
+                pos += 1;
+            },
+            _ if is_ident_char(ch) || ch == '$' => {
+                let len = rest.find(|c: char| !is_ident_char(c) && c != '$').unwrap_or(rest.len());
+                let before = content[..pos].trim_end();
+                let is_property = before.ends_with('.') && !before.ends_with("...");
+                expression_start = !is_property && EXPRESSION_KEYWORDS.contains(&&rest[..len]);
+                pos += len;
+            },
+            _ if ch.is_whitespace() => pos += ch.len_utf8(),
+            // postfix i++ and j--, the value before them still ends the expression
+            '+' | '-' if !expression_start && rest[1..].starts_with(ch) => pos += 2,
+            _ => {
+                expression_start = true;
+                pos += ch.len_utf8();
+            }
+        }
+    }
+
+    if in_braces { None } else { Some(content.len()) }
+}
+
+// the `//` or `/* */` comment starting at `pos`, if any
+fn comment_at(content: &str, pos: usize) -> Option<CommentMatch> {
+    let rest = &content[pos..];
+    let (end_byte, comment_type) = if rest.starts_with("//") {
+        (line_end(content, pos), CommentType::SingleLine)
+    } else if let Some(body) = rest.strip_prefix("/*") {
+        (body.find("*/").map_or(content.len(), |i| pos + 2 + i + 2), CommentType::MultiLine)
+    } else {
+        return None;
+    };
+    Some(CommentMatch { start_byte: pos, end_byte, comment_type })
+}
+
+// the end of a '...' or "..." string whose body starts at `pos`; an unterminated
+// one ends with its line
+fn string_end(content: &str, pos: usize, quote: char) -> usize {
+    let mut chars = content[pos..].char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => { chars.next(); },
+            '\n' => return pos + i,
+            _ if ch == quote => return pos + i + 1,
+            _ => {}
+        }
+    }
+    content.len()
+}
+
+// the end of a template literal whose body starts at `pos`, comments in its
+// `${}` expressions are collected; None if the file ends inside one
+fn template_end(content: &str, mut pos: usize, comments: &mut Vec<CommentMatch>) -> Option<usize> {
+    while let Some(offset) = content[pos..].find(['\\', '`', '$']) {
+        let at = pos + offset;
+        pos = match content.as_bytes()[at] {
+            b'\\' => at + 1 + content[at + 1..].chars().next().map_or(0, char::len_utf8),
+            b'`' => return Some(at + 1),
+            _ if content[at..].starts_with("${") => scan_code(content, at + 2, true, comments)?,
+            _ => at + 1,
+        };
+    }
+    Some(content.len())
+}
+
+// the end of a regex literal whose body starts at `pos`, flags included. A `/`
+// inside a [character class] doesn't end it
+fn regex_end(content: &str, pos: usize) -> Option<usize> {
+    let mut in_class = false;
+    let mut chars = content[pos..].char_indices();
+
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => { chars.next(); },
+            '\n' => return None,
+            '[' => in_class = true,
+            ']' => in_class = false,
+            '/' if !in_class => {
+                let end = pos + i + 1;
+                return Some(end + content[end..].find(|c: char| !is_ident_char(c)).unwrap_or(content.len() - end));
+            },
+            _ => {}
+        }
+    }
+    None
+}
+
+// the end of the JSX element or fragment starting with the `<` at `start`, None
+// if it isn't one; comments found while trying are dropped again in that case
+fn jsx_element_end(content: &str, start: usize, comments: &mut Vec<CommentMatch>) -> Option<usize> {
+    let found = comments.len();
+    let end = jsx_element(content, start, comments);
+    if end.is_none() {
+        comments.truncate(found);
+    }
+    end
+}
+
+fn jsx_element(content: &str, start: usize, comments: &mut Vec<CommentMatch>) -> Option<usize> {
+    let name_end = jsx_name_end(content, start + 1);
+    let name = &content[start + 1..name_end];
+    // `<>` opens a fragment
+    let valid_name = name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        || name.is_empty() && content[name_end..].starts_with('>');
+    if !valid_name {
+        return None;
+    }
+
+    let (mut pos, self_closing) = jsx_tag_end(content, name_end, comments)?;
+    if self_closing {
+        return Some(pos);
+    }
+
+    // children: text, {expressions} and nested elements up to the closing tag
+    loop {
+        let at = pos + content[pos..].find(['{', '}', '<'])?;
+        pos = match content.as_bytes()[at] {
+            b'{' => scan_code(content, at + 1, true, comments)?,
+            b'}' => return None,
+            _ if content[at..].starts_with("</") => {
+                let closing_name_end = jsx_name_end(content, at + 2);
+                if content[at + 2..closing_name_end] != *name {
+                    return None;
+                }
+                let after = content[closing_name_end..].trim_start().strip_prefix('>')?;
+                return Some(content.len() - after.len());
+            },
+            _ => jsx_element(content, at, comments)?,
+        };
+    }
+}
+
+// tag and attribute names like `Foo.Bar`, `data-id` or `xlink:href`
+fn jsx_name_end(content: &str, pos: usize) -> usize {
+    content[pos..]
+        .find(|c: char| !is_ident_char(c) && !matches!(c, '$' | '.' | '-' | ':'))
+        .map_or(content.len(), |i| pos + i)
+}
+
+// the end of a start tag's attributes at `pos` and whether the tag was `/>`
+fn jsx_tag_end(content: &str, mut pos: usize, comments: &mut Vec<CommentMatch>) -> Option<(usize, bool)> {
+    loop {
+        let rest = &content[pos..];
+        let ch = rest.chars().next()?;
+
+        pos = if rest.starts_with("/>") {
+            return Some((pos + 2, true));
+        } else if ch == '>' {
+            return Some((pos + 1, false));
+        } else if let Some(comment) = comment_at(content, pos) {
+            let end = comment.end_byte;
+            comments.push(comment);
+            end
+        } else if ch.is_whitespace() || ch == '=' {
+            pos + ch.len_utf8()
+        } else if ch == '"' || ch == '\'' {
+            // no escapes in attribute strings
+            pos + 1 + rest[1..].find(ch)? + 1
+        } else if ch == '{' {
+            scan_code(content, pos + 1, true, comments)?
+        } else if ch == '<' {
+            jsx_element(content, pos, comments)?
+        } else if is_ident_char(ch) || ch == '$' {
+            jsx_name_end(content, pos)
+        } else {
+            return None;
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comment_texts(content: &str) -> Vec<&str> {
+        extract_javascript_comments(content)
+            .iter()
+            .map(|comment| &content[comment.start_byte..comment.end_byte])
+            .collect()
+    }
+
+    #[test]
+    fn division_after_postfix_increment() {
+        assert_eq!(comment_texts("let q = i++ / 2; // half"), ["// half"]);
+        assert_eq!(comment_texts("let r = j-- / 2 /* blk */"), ["/* blk */"]);
+        assert_eq!(comment_texts("let s = a[0]++ / n; // idx"), ["// idx"]);
+        assert_eq!(comment_texts("let t = f()-- / n; // call"), ["// call"]);
+    }
+
+    #[test]
+    fn prefix_increment_then_division() {
+        assert_eq!(comment_texts("let p = ++i / 2; // pre"), ["// pre"]);
+    }
+
+    #[test]
+    fn division() {
+        assert_eq!(comment_texts("let d = a / b; // div"), ["// div"]);
+        assert_eq!(comment_texts("let e = (a + b) / 2 / c; /* avg */"), ["/* avg */"]);
+        assert_eq!(comment_texts("let f = x[1] / y // ratio"), ["// ratio"]);
+    }
+
+    #[test]
+    fn regex_literals() {
+        assert_eq!(comment_texts("let re = /\\/\\/ not a comment/; // real"), ["// real"]);
+        assert_eq!(comment_texts("if (ok) return /[/*]/.test(s); // class"), ["// class"]);
+        assert_eq!(comment_texts("x = a + /b\\/c/g; // after operator"), ["// after operator"]);
+    }
+}
@@ -0,0 +1,178 @@
+// Kotlin, Scala and Groovy: Java's comments, plus block comments that nest
+// (Kotlin, Scala), triple-quoted strings and ${} string templates
+use super::scan::{char_literal_end, hash_comment, is_ident_char, line_end, nested_comment_end};
+use super::{CommentMatch, CommentType};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Dialect {
+    Kotlin,
+    Scala,
+    Groovy,
+}
+
+// keywords after which a Groovy `/` starts a slashy string rather than dividing
+const GROOVY_EXPRESSION_KEYWORDS: &[&str] = &["return", "in", "case", "assert", "throw", "else", "yield"];
+
+pub fn extract_kotlin_comments(content: &str) -> Vec<CommentMatch> {
+    extract_jvm_comments(content, Dialect::Kotlin)
+}
+
+pub fn extract_scala_comments(content: &str) -> Vec<CommentMatch> {
+    extract_jvm_comments(content, Dialect::Scala)
+}
+
+pub fn extract_groovy_comments(content: &str) -> Vec<CommentMatch> {
+    extract_jvm_comments(content, Dialect::Groovy)
+}
+
+fn extract_jvm_comments(content: &str, dialect: Dialect) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+    let mut expression_start = true; // no value right before, so a Groovy `/` starts a slashy string
+
+    // #!/usr/bin/env kotlin, groovy or scala scripts
+    if content.starts_with("#!") {
+        pos = line_end(content, 0);
+        comments.push(hash_comment(content, 0, pos));
+    }
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        if rest.starts_with("//") {
+            let end = line_end(content, pos);
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+            pos = end;
+            continue;
+        }
+        if let Some(body) = rest.strip_prefix("/*") {
+            let end = if dialect == Dialect::Groovy {
+                body.find("*/").map_or(content.len(), |i| pos + 2 + i + 2)
+            } else {
+                nested_comment_end(content, pos, "/*", "*/")
+            };
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::MultiLine });
+            pos = end;
+            continue;
+        }
+        if ch.is_whitespace() {
+            pos += ch.len_utf8();
+            continue;
+        }
+
+        let mut value = true; // whether the token ends a value
+        pos = match ch {
+            '"' => string_end(content, pos, '"', dialect),
+            '\'' if dialect == Dialect::Groovy => string_end(content, pos, '\'', dialect),
+            // in Scala also 'symbols and '{ quotes }, which stay in code
+            '\'' => char_literal_end(content, pos).unwrap_or(pos + 1),
+            // `names with spaces`
+            '`' if dialect != Dialect::Groovy => {
+                content[pos + 1..line_end(content, pos)].find('`').map_or(pos + 1, |i| pos + 1 + i + 1)
+            },
+            '$' if dialect == Dialect::Groovy && rest.starts_with("$/") => dollar_slashy_end(content, pos + 2),
+            '/' if dialect == Dialect::Groovy && expression_start => {
+                slashy_end(content, pos + 1).unwrap_or_else(|| {
+                    value = false;
+                    pos + 1
+                })
+            },
+            _ if is_ident_char(ch) => {
+                let len = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+                value = !GROOVY_EXPRESSION_KEYWORDS.contains(&&rest[..len]);
+                pos + len
+            },
+            ')' | ']' | '}' => pos + 1,
+            _ => {
+                value = false;
+                pos + ch.len_utf8()
+            }
+        };
+        expression_start = !value;
+    }
+
+    comments
+}
+
+// the end of the string whose opening quote is at `start`. Triple-quoted
+// strings span lines and, except in Groovy, take no escapes. Double-quoted ones
+// may contain ${...} templates, in Scala only behind an interpolator: s"${x}"
+fn string_end(content: &str, start: usize, quote: char, dialect: Dialect) -> usize {
+    let triple_quote = if quote == '"' { "\"\"\"" } else { "'''" };
+    let triple = content[start..].starts_with(triple_quote);
+    let escapes = !triple || dialect == Dialect::Groovy;
+    let templates = quote == '"' && (dialect != Dialect::Scala || content[..start].ends_with(is_ident_char));
+    let mut pos = start + if triple { 3 } else { 1 };
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+        pos = match ch {
+            '\\' if escapes => pos + 1 + rest[1..].chars().next().map_or(0, char::len_utf8),
+            '\n' if !triple => return pos,
+            '$' if templates && rest.starts_with("${") => template_end(content, pos + 2, dialect),
+            // quotes right before the closing ones belong to the string: """a""""
+            _ if triple && rest.starts_with(triple_quote) => return pos + rest.len() - rest.trim_start_matches(quote).len(),
+            _ if !triple && ch == quote => return pos + 1,
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    content.len()
+}
+
+// the end of a ${...} template whose body starts at `pos`; strings in it may
+// contain braces and quotes of their own
+fn template_end(content: &str, mut pos: usize, dialect: Dialect) -> usize {
+    let mut depth = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        pos = match ch {
+            '}' if depth == 0 => return pos + 1,
+            '}' => {
+                depth -= 1;
+                pos + 1
+            },
+            '{' => {
+                depth += 1;
+                pos + 1
+            },
+            '"' => string_end(content, pos, '"', dialect),
+            '\'' if dialect == Dialect::Groovy => string_end(content, pos, '\'', dialect),
+            '\'' => char_literal_end(content, pos).unwrap_or(pos + 1),
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    content.len()
+}
+
+// Groovy's /slashy/ strings, where only `\/` is an escape. Like regexes in
+// other extractors only single-line ones count, so a misread division can't
+// swallow the file
+fn slashy_end(content: &str, body_start: usize) -> Option<usize> {
+    let line = &content[body_start..line_end(content, body_start)];
+    let mut chars = line.char_indices();
+
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => { chars.next(); },
+            '/' => return Some(body_start + i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+// Groovy's $/dollar slashy/$ strings, `$$` and `$/` are escapes
+fn dollar_slashy_end(content: &str, body_start: usize) -> usize {
+    let mut pos = body_start;
+    while let Some(offset) = content[pos..].find(['$', '/']) {
+        let at = pos + offset;
+        let rest = &content[at..];
+        if rest.starts_with("/$") {
+            return at + 2;
+        }
+        pos = if rest.starts_with("$$") || rest.starts_with("$/") { at + 2 } else { at + 1 };
+    }
+    content.len()
+}
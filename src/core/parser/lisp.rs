@@ -0,0 +1,116 @@
+// Common Lisp, Emacs Lisp, Scheme, Racket and Clojure: `;` line comments,
+// nesting `#| |#` blocks, `#;` and `#_` datum comments and Clojure's
+// (comment ...) forms, which are reported whole
+use super::scan::{delimited_end, line_end, nested_comment_end};
+use super::{CommentMatch, CommentType};
+
+#[derive(Clone, Copy, PartialEq)]
+enum Dialect {
+    Lisp,
+    Clojure,
+}
+
+pub fn extract_lisp_comments(content: &str) -> Vec<CommentMatch> {
+    extract_lisp_family_comments(content, Dialect::Lisp)
+}
+
+pub fn extract_clojure_comments(content: &str) -> Vec<CommentMatch> {
+    extract_lisp_family_comments(content, Dialect::Clojure)
+}
+
+fn extract_lisp_family_comments(content: &str, dialect: Dialect) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        let block_end = if dialect == Dialect::Lisp && rest.starts_with("#|") {
+            Some(nested_comment_end(content, pos, "#|", "|#"))
+        } else if rest.starts_with("#;") && dialect == Dialect::Lisp || rest.starts_with("#_") && dialect == Dialect::Clojure {
+            // the datum after it is commented out
+            Some(datum_end(content, pos + 2, dialect))
+        } else if dialect == Dialect::Clojure && is_comment_form(rest) {
+            Some(datum_end(content, pos, dialect))
+        } else {
+            None
+        };
+        if let Some(end) = block_end {
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::MultiLine });
+            pos = end;
+            continue;
+        }
+
+        pos = match ch {
+            ';' => {
+                let end = line_end(content, pos);
+                comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+                end
+            },
+            _ => token_end(content, pos, dialect),
+        };
+    }
+
+    comments
+}
+
+// (comment ...), but not (comments ...) or (comment-out ...)
+fn is_comment_form(rest: &str) -> bool {
+    rest.strip_prefix("(comment")
+        .is_some_and(|after| after.is_empty() || after.starts_with(|c: char| c.is_whitespace() || c == ')'))
+}
+
+// the end of the string, char literal or other single char at `pos`, with the
+// char literals that could hide a `;` or quote: #\; and ?; in Lisp, \; in Clojure
+fn token_end(content: &str, pos: usize, dialect: Dialect) -> usize {
+    let rest = &content[pos..];
+    let char_after = |skip: usize| rest[skip..].chars().next().map_or(0, char::len_utf8);
+    let at_token_start = content[..pos].ends_with(|c: char| c.is_whitespace() || "()[]{}'`,".contains(c)) || pos == 0;
+
+    match rest.chars().next() {
+        Some('"') => delimited_end(content, pos + 1, '"'),
+        Some('\\') if dialect == Dialect::Clojure => pos + 1 + char_after(1),
+        Some('#') if dialect == Dialect::Lisp && rest.starts_with("#\\") => pos + 2 + char_after(2),
+        // Emacs Lisp's ?a and ?\;
+        Some('?') if dialect == Dialect::Lisp && at_token_start && rest[1..].starts_with('\\') => pos + 2 + char_after(2),
+        Some('?') if dialect == Dialect::Lisp && at_token_start => pos + 1 + char_after(1),
+        Some(ch) => pos + ch.len_utf8(),
+        None => pos,
+    }
+}
+
+// the end of the datum after `pos`: a bracketed form, a string or an atom,
+// after any reader prefixes like ' ` , @ # ^
+fn datum_end(content: &str, pos: usize, dialect: Dialect) -> usize {
+    let start = pos + (content[pos..].len() - content[pos..].trim_start().len());
+    let rest = content[start..].trim_start_matches(['\'', '`', ',', '@', '#', '^', '~']);
+    let mut pos = content.len() - rest.len();
+
+    match rest.chars().next() {
+        Some('(' | '[' | '{') => {},
+        Some('"') => return delimited_end(content, pos + 1, '"'),
+        _ => return pos + rest.find(|c: char| c.is_whitespace() || "()[]{}".contains(c)).unwrap_or(rest.len()),
+    }
+
+    let mut depth = 0;
+    while let Some(ch) = content[pos..].chars().next() {
+        pos = match ch {
+            '(' | '[' | '{' => {
+                depth += 1;
+                pos + 1
+            },
+            ')' | ']' | '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return pos + 1;
+                }
+                pos + 1
+            },
+            ';' => line_end(content, pos),
+            '#' if dialect == Dialect::Lisp && content[pos..].starts_with("#|") => nested_comment_end(content, pos, "#|", "|#"),
+            _ => token_end(content, pos, dialect),
+        };
+    }
+
+    content.len()
+}
@@ -0,0 +1,68 @@
+// `--` comments and --[[ long comments ]], whose level (the `=` between the
+// brackets, as in --[==[ ]==]) must match to close them. [[Long strings]] use
+// the same brackets
+use super::scan::{hash_comment, line_end};
+use super::{CommentMatch, CommentType};
+
+pub fn extract_lua_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    // #!/usr/bin/env lua
+    if content.starts_with("#!") {
+        pos = line_end(content, 0);
+        comments.push(hash_comment(content, 0, pos));
+    }
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        pos = if rest.starts_with("--") {
+            if let Some(end) = long_bracket_end(content, pos + 2) {
+                comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::MultiLine });
+                end
+            } else {
+                let end = line_end(content, pos);
+                comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+                end
+            }
+        } else if ch == '[' {
+            long_bracket_end(content, pos).unwrap_or(pos + 1)
+        } else if ch == '"' || ch == '\'' {
+            string_end(content, pos + 1, ch)
+        } else {
+            pos + ch.len_utf8()
+        };
+    }
+
+    comments
+}
+
+// for a [[ or [==[ opening bracket at `pos`, the end of the matching ]] or ]==];
+// None if there's no long bracket at `pos`
+fn long_bracket_end(content: &str, pos: usize) -> Option<usize> {
+    let rest = content[pos..].strip_prefix('[')?;
+    let level = rest.len() - rest.trim_start_matches('=').len();
+    if !rest[level..].starts_with('[') {
+        return None;
+    }
+
+    let closing = format!("]{}]", "=".repeat(level));
+    let body = pos + level + 2;
+    Some(content[body..].find(&closing).map_or(content.len(), |i| body + i + closing.len()))
+}
+
+// the end of a quoted string whose body starts at `pos`; `\` escapes include
+// line breaks, an unescaped one ends an unterminated string
+fn string_end(content: &str, pos: usize, quote: char) -> usize {
+    let mut chars = content[pos..].char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => { chars.next(); },
+            '\n' => return pos + i,
+            _ if ch == quote => return pos + i + 1,
+            _ => {}
+        }
+    }
+    content.len()
+}
@@ -0,0 +1,230 @@
+// <!-- --> comments in HTML, XML and Markdown, plus the embedded <script> and
+// <style> blocks of HTML pages and Vue/Svelte components
+use super::scan::line_end;
+use super::{extract_comments_from_content, CommentMatch, CommentType, Language};
+
+// HTML, Vue and Svelte: <script> and <style> bodies go to the JS/TS and CSS
+// extractors, with positions relative to the whole file
+pub fn extract_html_comments(content: &str) -> Vec<CommentMatch> {
+    extract_markup_comments(content, true)
+}
+
+pub fn extract_xml_comments(content: &str) -> Vec<CommentMatch> {
+    extract_markup_comments(content, false)
+}
+
+fn extract_markup_comments(content: &str, embedded_code: bool) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    while let Some(offset) = content[pos..].find('<') {
+        let start = pos + offset;
+        let rest = &content[start..];
+
+        pos = if rest.starts_with("<!--") {
+            let end = html_comment_end(content, start);
+            comments.push(CommentMatch {
+                start_byte: start,
+                end_byte: end,
+                comment_type: CommentType::MultiLine,
+            });
+            end
+        } else if rest.starts_with("<![CDATA[") {
+            rest.find("]]>").map_or(content.len(), |i| start + i + 3)
+        } else if rest.starts_with("<?") {
+            rest.find("?>").map_or(content.len(), |i| start + i + 2)
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '!') {
+            let tag_end = tag_end(content, start);
+            let tag = &content[start..tag_end];
+            let name = tag_name(tag);
+
+            if embedded_code && !tag.ends_with("/>") && (name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style")) {
+                let body_end = closing_tag_start(content, tag_end, name);
+                let body = &content[tag_end..body_end];
+                let language = if name.eq_ignore_ascii_case("style") {
+                    Some(style_language(tag))
+                } else {
+                    script_language(tag)
+                };
+                let embedded = language.map_or_else(Vec::new, |language| extract_comments_from_content(body, language));
+
+                comments.extend(embedded.into_iter().map(|comment| CommentMatch {
+                    start_byte: tag_end + comment.start_byte,
+                    end_byte: tag_end + comment.end_byte,
+                    comment_type: comment.comment_type,
+                }));
+                body_end
+            } else {
+                tag_end
+            }
+        } else {
+            start + 1
+        };
+    }
+
+    comments
+}
+
+// the end of the `-->` closing a comment that starts at `start`; `<!-->` and
+// `<!--->` are empty comments
+fn html_comment_end(content: &str, start: usize) -> usize {
+    let body = start + 4;
+    if content[body..].starts_with('>') {
+        return body + 1;
+    }
+    if content[body..].starts_with("->") {
+        return body + 2;
+    }
+    content[body..].find("-->").map_or(content.len(), |i| body + i + 3)
+}
+
+// the end of a start tag, quoted attribute values may contain `>`
+fn tag_end(content: &str, start: usize) -> usize {
+    let mut quote: Option<char> = None;
+    for (i, ch) in content[start..].char_indices() {
+        match quote {
+            Some(q) if ch == q => quote = None,
+            Some(_) => {},
+            None if ch == '"' || ch == '\'' => quote = Some(ch),
+            None if ch == '>' => return start + i + 1,
+            None => {}
+        }
+    }
+    content.len()
+}
+
+// "<script lang=\"ts\">" -> "script"
+fn tag_name(tag: &str) -> &str {
+    let name = &tag[1..];
+    let len = name.find(|c: char| c.is_whitespace() || c == '/' || c == '>').unwrap_or(name.len());
+    &name[..len]
+}
+
+// where the `</name` closing a raw text element starts, tags inside it don't count
+fn closing_tag_start(content: &str, pos: usize, name: &str) -> usize {
+    content[pos..]
+        .match_indices("</")
+        .map(|(i, _)| pos + i)
+        .find(|&i| content[i + 2..].get(..name.len()).is_some_and(|n| n.eq_ignore_ascii_case(name)))
+        .unwrap_or(content.len())
+}
+
+// None for non-code scripts like JSON data or templates
+fn script_language(tag: &str) -> Option<Language> {
+    if let Some(lang) = attribute(tag, "lang") {
+        return match lang.to_ascii_lowercase().as_str() {
+            "ts" | "tsx" | "typescript" => Some(Language::TypeScript),
+            _ => Some(Language::JavaScript),
+        };
+    }
+
+    match attribute(tag, "type").map(|t| t.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("" | "module" | "text/javascript" | "application/javascript" | "text/babel" | "text/jsx") => Some(Language::JavaScript),
+        Some("text/typescript" | "application/typescript") => Some(Language::TypeScript),
+        Some(_) => None,
+    }
+}
+
+// <style lang="scss"> in Vue and Svelte components
+fn style_language(tag: &str) -> Language {
+    match attribute(tag, "lang").map(|lang| lang.to_ascii_lowercase()).as_deref() {
+        Some("scss" | "sass") => Language::Scss,
+        Some("less") => Language::Less,
+        _ => Language::Css,
+    }
+}
+
+// the value of attribute `name` in a start tag, "" for attributes without one
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let inner = tag.trim_start_matches('<').trim_end_matches('>').trim_end_matches('/');
+    let mut rest = &inner[tag_name(tag).len()..];
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+
+        let name_len = rest.find(|c: char| c.is_whitespace() || c == '=').unwrap_or(rest.len()).max(1);
+        let (attribute_name, after) = rest.split_at(name_len);
+        let (value, remaining) = match after.trim_start().strip_prefix('=') {
+            Some(value) => {
+                let value = value.trim_start();
+                match value.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = value[1..].find(quote).map_or(value.len(), |i| i + 1);
+                        (&value[1..end], &value[(end + 1).min(value.len())..])
+                    },
+                    _ => {
+                        let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                        value.split_at(end)
+                    }
+                }
+            },
+            None => ("", after),
+        };
+
+        if attribute_name.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+        rest = remaining;
+    }
+}
+
+// HTML comments outside fenced code blocks and `code spans`
+pub fn extract_markdown_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut fence: Option<(char, usize)> = None; // the char and length of the open ``` or ~~~ fence
+    let mut pos = 0; // at a line start at the top of the loop
+
+    while pos < content.len() {
+        let end = line_end(content, pos);
+        let line = content[pos..end].trim_start();
+
+        if let Some((fence_char, fence_len)) = fence {
+            let closing_len = line.len() - line.trim_start_matches(fence_char).len();
+            if closing_len >= fence_len && line[closing_len..].trim().is_empty() {
+                fence = None;
+            }
+            pos = end + 1;
+            continue;
+        }
+        if let Some(fence_char) = line.chars().next().filter(|&c| c == '`' || c == '~') {
+            let fence_len = line.len() - line.trim_start_matches(fence_char).len();
+            if fence_len >= 3 {
+                fence = Some((fence_char, fence_len));
+                pos = end + 1;
+                continue;
+            }
+        }
+
+        // a comment may end on a later line, scanning resumes right after it
+        let mut i = pos;
+        loop {
+            let end = line_end(content, i);
+            let Some(offset) = content[i..end].find(['`', '<']) else {
+                pos = end + 1;
+                break;
+            };
+            let at = i + offset;
+
+            i = if content[at..].starts_with('`') {
+                let run = content[at..end].len() - content[at..end].trim_start_matches('`').len();
+                let ticks = &content[at..at + run];
+                content[at + run..end].find(ticks).map_or(at + run, |close| at + run + close + run)
+            } else if content[at..].starts_with("<!--") {
+                let comment_end = html_comment_end(content, at);
+                comments.push(CommentMatch {
+                    start_byte: at,
+                    end_byte: comment_end,
+                    comment_type: CommentType::MultiLine,
+                });
+                comment_end
+            } else {
+                at + 1
+            };
+        }
+    }
+
+    comments
+}
@@ -0,0 +1,414 @@
+mod beam;
+mod c;
+mod config;
+mod css;
+mod dart;
+mod doc;
+mod dotnet;
+mod go;
+mod haskell;
+mod java;
+mod javascript;
+mod jvm;
+mod lisp;
+mod lua;
+mod markup;
+mod ocaml;
+mod perl;
+mod php;
+mod python;
+mod r;
+mod ruby;
+mod rust;
+mod scan;
+mod shell;
+mod spec;
+mod sql;
+mod swift;
+#[cfg(feature = "tree-sitter")]
+mod treesitter;
+
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+pub use spec::{load_languages, LanguageSpec};
+
+// which extractors this build uses for the languages tree-sitter covers
+pub const PARSER_BACKEND: &str = if cfg!(feature = "tree-sitter") { "tree-sitter" } else { "builtin" };
+
+use beam::{extract_elixir_comments, extract_erlang_comments};
+use c::extract_c_comments;
+use config::{extract_dockerfile_comments, extract_makefile_comments, extract_toml_comments, extract_yaml_comments};
+use css::{extract_css_comments, extract_scss_comments};
+use dart::extract_dart_comments;
+use doc::classify_doc_comments;
+use dotnet::{extract_csharp_comments, extract_fsharp_comments};
+use go::extract_go_comments;
+use haskell::extract_haskell_comments;
+use java::extract_java_comments;
+use javascript::extract_javascript_comments;
+use jvm::{extract_groovy_comments, extract_kotlin_comments, extract_scala_comments};
+use lisp::{extract_clojure_comments, extract_lisp_comments};
+use lua::extract_lua_comments;
+use markup::{extract_html_comments, extract_markdown_comments, extract_xml_comments};
+use ocaml::extract_ocaml_comments;
+use perl::extract_perl_comments;
+use php::extract_php_comments;
+use python::extract_python_comments;
+use r::extract_r_comments;
+use ruby::extract_ruby_comments;
+use rust::extract_rust_comments;
+use shell::extract_shell_comments;
+use spec::extract_spec_comments;
+use sql::extract_sql_comments;
+use swift::extract_swift_comments;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Java,
+    Python,
+    JavaScript,
+    TypeScript,
+    Rust,
+    C,
+    Cpp,
+    ObjectiveC,
+    Go,
+    Shell,
+    Ruby,
+    Perl,
+    Yaml,
+    Toml,
+    Makefile,
+    Dockerfile,
+    R,
+    Html,
+    Xml,
+    Markdown,
+    Vue,
+    Svelte,
+    Css,
+    Scss,
+    Less,
+    Sql,
+    Kotlin,
+    Scala,
+    Groovy,
+    CSharp,
+    FSharp,
+    Swift,
+    Dart,
+    Php,
+    Lua,
+    Haskell,
+    Elm,
+    OCaml,
+    Lisp, // Common Lisp, Emacs Lisp, Scheme and Racket
+    Clojure,
+    Erlang,
+    Elixir,
+    Custom(&'static LanguageSpec), // defined in a languages.toml file
+}
+
+impl Language {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::Java => "java",
+            Language::Python => "python",
+            Language::JavaScript => "javascript",
+            Language::TypeScript => "typescript",
+            Language::Rust => "rust",
+            Language::C => "c",
+            Language::Cpp => "cpp",
+            Language::ObjectiveC => "objective-c",
+            Language::Go => "go",
+            Language::Shell => "shell",
+            Language::Ruby => "ruby",
+            Language::Perl => "perl",
+            Language::Yaml => "yaml",
+            Language::Toml => "toml",
+            Language::Makefile => "makefile",
+            Language::Dockerfile => "dockerfile",
+            Language::R => "r",
+            Language::Html => "html",
+            Language::Xml => "xml",
+            Language::Markdown => "markdown",
+            Language::Vue => "vue",
+            Language::Svelte => "svelte",
+            Language::Css => "css",
+            Language::Scss => "scss",
+            Language::Less => "less",
+            Language::Sql => "sql",
+            Language::Kotlin => "kotlin",
+            Language::Scala => "scala",
+            Language::Groovy => "groovy",
+            Language::CSharp => "csharp",
+            Language::FSharp => "fsharp",
+            Language::Swift => "swift",
+            Language::Dart => "dart",
+            Language::Php => "php",
+            Language::Lua => "lua",
+            Language::Haskell => "haskell",
+            Language::Elm => "elm",
+            Language::OCaml => "ocaml",
+            Language::Lisp => "lisp",
+            Language::Clojure => "clojure",
+            Language::Erlang => "erlang",
+            Language::Elixir => "elixir",
+            Language::Custom(spec) => spec.name(),
+        }
+    }
+
+    // accepts the language name or its usual file extension, e.g. "python" or "py"
+    pub fn from_name(name: &str) -> Option<Language> {
+        let name = name.trim().to_lowercase();
+        if let Some(spec) = spec::languages().iter().find(|spec| spec.name() == name || spec.matches_extension(&name)) {
+            return Some(Language::Custom(spec));
+        }
+
+        match name.as_str() {
+            "java" => Some(Language::Java),
+            "python" | "py" => Some(Language::Python),
+            "javascript" | "js" | "jsx" => Some(Language::JavaScript),
+            "typescript" | "ts" | "tsx" => Some(Language::TypeScript),
+            "rust" | "rs" => Some(Language::Rust),
+            "c" | "h" => Some(Language::C),
+            "cpp" | "c++" | "cc" | "cxx" | "hpp" => Some(Language::Cpp),
+            "objective-c" | "objc" | "m" | "mm" => Some(Language::ObjectiveC),
+            "go" | "golang" => Some(Language::Go),
+            "shell" | "sh" | "bash" | "zsh" => Some(Language::Shell),
+            "ruby" | "rb" => Some(Language::Ruby),
+            "perl" | "pl" | "pm" => Some(Language::Perl),
+            "yaml" | "yml" => Some(Language::Yaml),
+            "toml" => Some(Language::Toml),
+            "makefile" | "make" | "mk" => Some(Language::Makefile),
+            "dockerfile" | "docker" => Some(Language::Dockerfile),
+            "r" => Some(Language::R),
+            "html" | "htm" => Some(Language::Html),
+            "xml" | "svg" => Some(Language::Xml),
+            "markdown" | "md" => Some(Language::Markdown),
+            "vue" => Some(Language::Vue),
+            "svelte" => Some(Language::Svelte),
+            "css" => Some(Language::Css),
+            "scss" | "sass" => Some(Language::Scss),
+            "less" => Some(Language::Less),
+            "sql" => Some(Language::Sql),
+            "kotlin" | "kt" | "kts" => Some(Language::Kotlin),
+            "scala" => Some(Language::Scala),
+            "groovy" | "gradle" => Some(Language::Groovy),
+            "csharp" | "c#" | "cs" => Some(Language::CSharp),
+            "fsharp" | "f#" | "fs" => Some(Language::FSharp),
+            "swift" => Some(Language::Swift),
+            "dart" => Some(Language::Dart),
+            "php" => Some(Language::Php),
+            "lua" => Some(Language::Lua),
+            "haskell" | "hs" => Some(Language::Haskell),
+            "elm" => Some(Language::Elm),
+            "ocaml" | "ml" => Some(Language::OCaml),
+            "lisp" | "common-lisp" | "elisp" | "emacs-lisp" | "el" | "scheme" | "scm" | "racket" | "rkt" => Some(Language::Lisp),
+            "clojure" | "clj" | "cljs" => Some(Language::Clojure),
+            "erlang" | "erl" => Some(Language::Erlang),
+            "elixir" | "ex" | "exs" => Some(Language::Elixir),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommentMatch {
+    pub start_byte: usize,
+    pub end_byte: usize,
+    pub comment_type: CommentType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommentType {
+    SingleLine, // single line - language agnostic
+    MultiLine, // multiline - language agnostic
+    Directive, // tool directive like //go:generate or a #! line, not prose
+    Docstring, // documentation string like a Python docstring
+    Doc, // documentation comment like Javadoc's /** */ or Haddock's -- |
+}
+
+impl CommentType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CommentType::SingleLine => "line",
+            CommentType::MultiLine => "block",
+            CommentType::Directive => "directive",
+            CommentType::Docstring => "docstring",
+            CommentType::Doc => "doc",
+        }
+    }
+}
+
+pub fn extract_comments_from_content(content: &str, language: Language) -> Vec<CommentMatch> {
+    let mut comments = extract_language_comments(content, language);
+    classify_doc_comments(content, language, &mut comments);
+    comments
+}
+
+fn extract_language_comments(content: &str, language: Language) -> Vec<CommentMatch> {
+    #[cfg(feature = "tree-sitter")]
+    if let Some(comments) = treesitter::extract_comments(content, language) {
+        return comments;
+    }
+
+    match language {
+        Language::Java => extract_java_comments(content),
+        Language::Python => extract_python_comments(content),
+        Language::JavaScript => extract_javascript_comments(content),
+        Language::TypeScript => extract_javascript_comments(content), // same lexer, type syntax has no comments of its own
+        Language::Rust => extract_rust_comments(content),
+        Language::C | Language::Cpp | Language::ObjectiveC => extract_c_comments(content),
+        Language::Go => extract_go_comments(content),
+        Language::Shell => extract_shell_comments(content),
+        Language::Ruby => extract_ruby_comments(content),
+        Language::Perl => extract_perl_comments(content),
+        Language::Yaml => extract_yaml_comments(content),
+        Language::Toml => extract_toml_comments(content),
+        Language::Makefile => extract_makefile_comments(content),
+        Language::Dockerfile => extract_dockerfile_comments(content),
+        Language::R => extract_r_comments(content),
+        Language::Html | Language::Vue | Language::Svelte => extract_html_comments(content),
+        Language::Xml => extract_xml_comments(content),
+        Language::Markdown => extract_markdown_comments(content),
+        Language::Css => extract_css_comments(content),
+        Language::Scss | Language::Less => extract_scss_comments(content),
+        Language::Sql => extract_sql_comments(content),
+        Language::Kotlin => extract_kotlin_comments(content),
+        Language::Scala => extract_scala_comments(content),
+        Language::Groovy => extract_groovy_comments(content),
+        Language::CSharp => extract_csharp_comments(content),
+        Language::FSharp => extract_fsharp_comments(content),
+        Language::Swift => extract_swift_comments(content),
+        Language::Dart => extract_dart_comments(content),
+        Language::Php => extract_php_comments(content),
+        Language::Lua => extract_lua_comments(content),
+        Language::Haskell | Language::Elm => extract_haskell_comments(content),
+        Language::OCaml => extract_ocaml_comments(content),
+        Language::Lisp => extract_lisp_comments(content),
+        Language::Clojure => extract_clojure_comments(content),
+        Language::Erlang => extract_erlang_comments(content),
+        Language::Elixir => extract_elixir_comments(content),
+        Language::Custom(spec) => extract_spec_comments(content, spec),
+    }
+}
+
+pub fn detect_language(file_path: &Path) -> Option<Language> {
+    let file_name = file_path.file_name()?.to_str()?;
+    let extension = file_path.extension().and_then(|e| e.to_str());
+
+    // data-driven languages first, so a user's definition can take over an extension
+    let custom = spec::languages().iter().find(|spec| {
+        spec.matches_file_name(file_name) || extension.is_some_and(|e| spec.matches_extension(e))
+    });
+    if let Some(spec) = custom {
+        return Some(Language::Custom(spec));
+    }
+
+    if let Some(language) = detect_language_from_file_name(file_name) {
+        return Some(language);
+    }
+
+    match extension? {
+        "java" => Some(Language::Java),
+        "py" => Some(Language::Python),
+        "js" | "mjs" | "cjs" | "jsx" => Some(Language::JavaScript),
+        "ts" | "tsx" | "mts" | "cts" => Some(Language::TypeScript),
+        "rs" => Some(Language::Rust),
+        "c" | "h" => Some(Language::C),
+        "cc" | "cpp" | "cxx" | "c++" | "hpp" | "hh" | "hxx" | "h++" => Some(Language::Cpp),
+        "m" | "mm" => Some(Language::ObjectiveC),
+        "go" => Some(Language::Go),
+        "sh" | "bash" | "zsh" | "ksh" => Some(Language::Shell),
+        "rb" | "rake" | "gemspec" | "ru" => Some(Language::Ruby),
+        "pl" | "pm" | "pod" => Some(Language::Perl),
+        "yml" | "yaml" => Some(Language::Yaml),
+        "toml" => Some(Language::Toml),
+        "mk" | "mak" => Some(Language::Makefile),
+        "dockerfile" => Some(Language::Dockerfile),
+        "r" | "R" => Some(Language::R),
+        "html" | "htm" | "xhtml" => Some(Language::Html),
+        "xml" | "svg" | "xsd" | "xsl" | "xslt" | "plist" => Some(Language::Xml),
+        "md" | "markdown" => Some(Language::Markdown),
+        "vue" => Some(Language::Vue),
+        "svelte" => Some(Language::Svelte),
+        "css" => Some(Language::Css),
+        "scss" | "sass" => Some(Language::Scss),
+        "less" => Some(Language::Less),
+        "sql" => Some(Language::Sql),
+        "kt" | "kts" => Some(Language::Kotlin),
+        "scala" | "sc" => Some(Language::Scala),
+        "groovy" | "gradle" | "gvy" => Some(Language::Groovy),
+        "cs" => Some(Language::CSharp),
+        "fs" | "fsx" | "fsi" => Some(Language::FSharp),
+        "swift" => Some(Language::Swift),
+        "dart" => Some(Language::Dart),
+        "php" | "phtml" => Some(Language::Php),
+        "lua" => Some(Language::Lua),
+        "hs" => Some(Language::Haskell),
+        "elm" => Some(Language::Elm),
+        "ml" | "mli" => Some(Language::OCaml),
+        "lisp" | "lsp" | "el" | "scm" | "ss" | "sld" | "rkt" => Some(Language::Lisp),
+        "clj" | "cljs" | "cljc" | "edn" => Some(Language::Clojure),
+        "erl" | "hrl" | "escript" => Some(Language::Erlang),
+        "ex" | "exs" => Some(Language::Elixir),
+        _ => None,
+    }
+}
+
+// files that are recognized by name rather than extension
+fn detect_language_from_file_name(file_name: &str) -> Option<Language> {
+    match file_name {
+        "Makefile" | "makefile" | "GNUmakefile" => Some(Language::Makefile),
+        "Dockerfile" | "Containerfile" => Some(Language::Dockerfile),
+        "Gemfile" | "Rakefile" | "Podfile" | "Vagrantfile" | "Guardfile" | "Brewfile" | "Fastfile" => Some(Language::Ruby),
+        ".bashrc" | ".bash_profile" | ".bash_logout" | ".zshrc" | ".zprofile" | ".zshenv" | ".profile" => Some(Language::Shell),
+        ".Rprofile" => Some(Language::R),
+        "Pipfile" => Some(Language::Toml),
+        "Jenkinsfile" => Some(Language::Groovy),
+        ".emacs" => Some(Language::Lisp),
+        "rebar.config" => Some(Language::Erlang),
+        _ if file_name.starts_with("Dockerfile.") || file_name.starts_with("Containerfile.") => Some(Language::Dockerfile),
+        _ => None,
+    }
+}
+
+// for scripts without an extension: "#!/bin/sh", "#!/usr/bin/env -S ruby -w"
+pub fn detect_language_from_shebang(content: &str) -> Option<Language> {
+    let line = content.lines().next()?.strip_prefix("#!")?;
+    let mut words = line.split_whitespace().map(|word| word.rsplit('/').next().unwrap_or(word));
+
+    let mut program = words.next()?;
+    if program == "env" {
+        // skip env's options and VAR=value assignments
+        program = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+    }
+
+    // python3.12 -> python
+    match program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.') {
+        "sh" | "bash" | "zsh" | "ksh" | "dash" | "ash" | "mksh" => Some(Language::Shell),
+        "python" => Some(Language::Python),
+        "ruby" => Some(Language::Ruby),
+        "perl" => Some(Language::Perl),
+        "Rscript" => Some(Language::R),
+        "node" => Some(Language::JavaScript),
+        "make" => Some(Language::Makefile),
+        "kotlin" => Some(Language::Kotlin),
+        "scala" => Some(Language::Scala),
+        "groovy" => Some(Language::Groovy),
+        "swift" => Some(Language::Swift),
+        "dart" => Some(Language::Dart),
+        "php" => Some(Language::Php),
+        "lua" | "luajit" => Some(Language::Lua),
+        "runghc" | "runhaskell" => Some(Language::Haskell),
+        "ocaml" => Some(Language::OCaml),
+        "guile" | "racket" | "sbcl" => Some(Language::Lisp),
+        "clojure" | "bb" => Some(Language::Clojure),
+        "escript" => Some(Language::Erlang),
+        "elixir" => Some(Language::Elixir),
+        _ => None,
+    }
+}
@@ -0,0 +1,87 @@
+// nesting `(* *)` comments, `(** *)` being ocamldoc ones. Like the compiler,
+// strings inside comments are lexed too, so a "*)" in one doesn't end the comment
+use super::scan::{char_literal_end, is_ident_char};
+use super::{CommentMatch, CommentType};
+
+pub fn extract_ocaml_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        pos = if rest.starts_with("(*") {
+            let end = comment_end(content, pos);
+            // (**) is empty and (*** a banner, neither is documentation
+            let is_doc = rest.starts_with("(**") && !rest.starts_with("(**)") && !rest.starts_with("(***");
+            let comment_type = if is_doc { CommentType::Doc } else { CommentType::MultiLine };
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type });
+            end
+        } else if let Some(end) = literal_end(content, pos) {
+            end
+        } else if is_ident_char(ch) {
+            // names may end in primes: x'
+            pos + rest.find(|c: char| !is_ident_char(c) && c != '\'').unwrap_or(rest.len())
+        } else {
+            pos + ch.len_utf8()
+        };
+    }
+
+    comments
+}
+
+// the end of the comment starting at `start`, nested comments included
+fn comment_end(content: &str, start: usize) -> usize {
+    let mut depth = 0;
+    let mut pos = start;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+        pos = if rest.starts_with("(*") {
+            depth += 1;
+            pos + 2
+        } else if rest.starts_with("*)") {
+            depth -= 1;
+            if depth == 0 {
+                return pos + 2;
+            }
+            pos + 2
+        } else if let Some(end) = literal_end(content, pos) {
+            end
+        } else {
+            pos + ch.len_utf8()
+        };
+    }
+
+    content.len()
+}
+
+// the end of a "string", {id|quoted string|id} or 'c' char literal at `pos`;
+// None for anything else, including type variables like 'a
+fn literal_end(content: &str, pos: usize) -> Option<usize> {
+    let rest = &content[pos..];
+    match rest.chars().next()? {
+        '"' => {
+            let mut chars = rest.char_indices().skip(1);
+            while let Some((i, ch)) = chars.next() {
+                match ch {
+                    '\\' => { chars.next(); },
+                    '"' => return Some(pos + i + 1),
+                    _ => {}
+                }
+            }
+            Some(content.len())
+        },
+        '{' => {
+            let id_len = rest[1..].find(|c: char| !c.is_ascii_lowercase() && c != '_').unwrap_or(rest.len() - 1);
+            if !rest[1 + id_len..].starts_with('|') {
+                return None;
+            }
+            let closing = format!("|{}}}", &rest[1..1 + id_len]);
+            let body = pos + 1 + id_len + 1;
+            Some(content[body..].find(&closing).map_or(content.len(), |i| body + i + closing.len()))
+        },
+        '\'' => char_literal_end(content, pos),
+        _ => None,
+    }
+}
@@ -0,0 +1,186 @@
+use super::scan::{
+    closing_bracket, delimited_end, hash_comment, heredoc_delimiter, is_ident_char, line_end,
+    next_line_start, skip_heredocs, skip_to,
+};
+use super::{CommentMatch, CommentType};
+
+enum PerlParseState {
+    Code,
+    LineComment,
+}
+
+pub fn extract_perl_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = PerlParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    let mut prev_ch = '\n'; // last char seen in code
+    let mut last_token = '\n'; // last non-whitespace char seen in code
+    let mut heredocs: Vec<(&str, bool)> = Vec::new(); // opened on this line, bodies start on the next
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            PerlParseState::Code => {
+                // after a value `/` is division, otherwise it starts a match
+                let after_value = is_ident_char(last_token) || matches!(last_token, ')' | ']' | '}' | '"' | '\'' | '`');
+                // `split /,/` passes a pattern to a function called without parens
+                let argument_start = prev_ch.is_whitespace()
+                    && chars.peek().is_some_and(|&(_, next)| !next.is_whitespace() && next != '=');
+                let starts_literal = !after_value || argument_start;
+
+                match ch {
+                    '#' if prev_ch != '$' => { // $#array is the last index
+                        comment_start = Some(byte_pos);
+                        state = PerlParseState::LineComment;
+                        continue;
+                    },
+                    '=' if prev_ch == '\n' && content[byte_pos + 1..].starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                        let end = pod_end(content, byte_pos);
+                        comments.push(CommentMatch {
+                            start_byte: byte_pos,
+                            end_byte: end,
+                            comment_type: CommentType::MultiLine,
+                        });
+                        skip_to(&mut chars, end);
+                        continue;
+                    },
+                    '_' if prev_ch == '\n' && matches!(&content[byte_pos..line_end(content, byte_pos)], "__END__" | "__DATA__") => {
+                        // the rest is data, but it's where modules usually keep their POD
+                        pod_after_end(content, next_line_start(content, byte_pos), &mut comments);
+                        break;
+                    },
+                    '\\' => { chars.next(); },
+                    '"' | '`' => skip_to(&mut chars, delimited_end(content, byte_pos + 1, ch)),
+                    '\'' if !is_ident_char(prev_ch) => skip_to(&mut chars, delimited_end(content, byte_pos + 1, ch)),
+                    '/' if starts_literal => {
+                        // only single-line patterns, so a misread division can't swallow the file
+                        let line = &content[..line_end(content, byte_pos)];
+                        let end = delimited_end(line, byte_pos + 1, '/');
+                        if line[..end].ends_with('/') && end > byte_pos + 1 {
+                            skip_to(&mut chars, end);
+                        }
+                    },
+                    '<' if content[byte_pos..].starts_with("<<") && starts_literal => {
+                        if let Some((heredoc, end)) = parse_heredoc(content, byte_pos + 2) {
+                            heredocs.push(heredoc);
+                            skip_to(&mut chars, end);
+                        }
+                    },
+                    _ if ch.is_ascii_alphabetic() && !is_ident_char(prev_ch) && !matches!(prev_ch, '$' | '@' | '%' | '&' | '-' | '>') => {
+                        // a whole word, so `this` isn't read as s///
+                        let word_len = content[byte_pos..].find(|c: char| !is_ident_char(c)).unwrap_or(content.len() - byte_pos);
+                        let word_end = byte_pos + word_len;
+                        skip_to(&mut chars, quote_like_end(content, &content[byte_pos..word_end], word_end).unwrap_or(word_end));
+                        prev_ch = 'a';
+                        last_token = 'a';
+                        continue;
+                    },
+                    '\n' => skip_heredocs(content, byte_pos + 1, &mut heredocs, &mut chars),
+                    _ => {}
+                }
+
+                prev_ch = ch;
+                if !ch.is_whitespace() {
+                    last_token = ch;
+                }
+            },
+            PerlParseState::LineComment => {
+                if ch == '\n' {
+                    comments.push(hash_comment(content, comment_start.take().unwrap(), byte_pos));
+                    skip_heredocs(content, byte_pos + 1, &mut heredocs, &mut chars);
+                    state = PerlParseState::Code;
+                    prev_ch = ch;
+                    last_token = ch;
+                }
+            }
+        }
+    }
+
+    if let (PerlParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(hash_comment(content, start, content.len()));
+    }
+
+    comments
+}
+
+// q(), qq{}, qw//, qr##, m!!, s{}{}, tr///, y///; `pos` is just past the word.
+// Returns the end of the last part, None if `word` isn't such an operator
+fn quote_like_end(content: &str, word: &str, pos: usize) -> Option<usize> {
+    let parts = match word {
+        "q" | "qq" | "qw" | "qr" | "m" => 1,
+        "s" | "tr" | "y" => 2,
+        _ => return None,
+    };
+
+    let open_pos = skip_whitespace(content, pos);
+    let open = content[open_pos..].chars().next()?;
+    // `q => 1` is a hash key, `q #` with a space starts a comment
+    if is_ident_char(open) || open.is_whitespace() || matches!(open, '=' | ',' | ';' | ')' | ']' | '}' | '>')
+        || (open == '#' && open_pos > pos)
+    {
+        return None;
+    }
+
+    let mut end = delimited_end(content, open_pos + open.len_utf8(), open);
+    if parts == 2 {
+        end = match closing_bracket(open) {
+            // s{...}{...}: the replacement has its own delimiters
+            Some(_) => {
+                let open_pos = skip_whitespace(content, end);
+                let open = content[open_pos..].chars().next()?;
+                delimited_end(content, open_pos + open.len_utf8(), open)
+            },
+            // s/.../.../: the middle delimiter is shared
+            None => delimited_end(content, end, open),
+        };
+    }
+
+    Some(end)
+}
+
+fn skip_whitespace(content: &str, pos: usize) -> usize {
+    content.len() - content[pos..].trim_start().len()
+}
+
+// POD runs from a `=word` line to the end of the `=cut` line
+fn pod_end(content: &str, pos: usize) -> usize {
+    let mut line_start = pos;
+    loop {
+        let end = line_end(content, line_start);
+        let line = &content[line_start..end];
+        if line == "=cut" || line.starts_with("=cut ") || end == content.len() {
+            return end;
+        }
+        line_start = end + 1;
+    }
+}
+
+fn pod_after_end(content: &str, mut pos: usize, comments: &mut Vec<CommentMatch>) {
+    while pos < content.len() {
+        if content[pos..].starts_with('=') && content[pos + 1..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+            let end = pod_end(content, pos);
+            comments.push(CommentMatch {
+                start_byte: pos,
+                end_byte: end,
+                comment_type: CommentType::MultiLine,
+            });
+            pos = end;
+        }
+        pos = next_line_start(content, pos);
+    }
+}
+
+// <<"EOF", <<'EOF', <<EOF, <<~EOF; `pos` is just past the `<<`
+fn parse_heredoc(content: &str, pos: usize) -> Option<((&str, bool), usize)> {
+    let rest = &content[pos..];
+    let indented = rest.starts_with('~');
+    let (delimiter, len) = heredoc_delimiter(&rest[usize::from(indented)..])?;
+
+    // 1 <<2 is a shift
+    if len == delimiter.len() && delimiter.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(((delimiter, indented), pos + usize::from(indented) + len))
+}
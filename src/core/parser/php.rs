@@ -0,0 +1,130 @@
+// `#`, `//` and `/* */` comments inside <?php ... ?> regions; everything
+// outside them is template output and skipped
+use super::scan::{hash_comment, is_ident_char, line_end};
+use super::{CommentMatch, CommentType};
+
+pub fn extract_php_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    // #!/usr/bin/env php, dropped by the CLI before the first region
+    if content.starts_with("#!") {
+        pos = line_end(content, 0);
+        comments.push(hash_comment(content, 0, pos));
+    }
+
+    while let Some(open) = region_start(content, pos) {
+        pos = scan_region(content, open, &mut comments);
+    }
+
+    comments
+}
+
+// where the code after the next <?php, <?= or <? opening tag starts; <?xml
+// declarations aren't PHP
+fn region_start(content: &str, pos: usize) -> Option<usize> {
+    content[pos..].match_indices("<?").map(|(i, _)| pos + i + 2).find_map(|body| {
+        let rest = &content[body..];
+        if rest.get(..3).is_some_and(|tag| tag.eq_ignore_ascii_case("php")) {
+            Some(body + 3)
+        } else if rest.starts_with('=') {
+            Some(body + 1)
+        } else if rest.starts_with(char::is_whitespace) {
+            Some(body)
+        } else {
+            None
+        }
+    })
+}
+
+// scans code from `pos` up to the `?>` ending the region, returns where it ends
+fn scan_region(content: &str, mut pos: usize, comments: &mut Vec<CommentMatch>) -> usize {
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        pos = if rest.starts_with("?>") {
+            return pos + 2;
+        } else if rest.starts_with("//") || (ch == '#' && !rest.starts_with("#[")) {
+            // `#[` starts an attribute. A line comment also ends at `?>`
+            let line = &content[pos..line_end(content, pos)];
+            let end = pos + line.find("?>").unwrap_or(line.len());
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+            end
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            let end = body.find("*/").map_or(content.len(), |i| pos + 2 + i + 2);
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::MultiLine });
+            end
+        } else if ch == '\'' || ch == '"' || ch == '`' {
+            string_end(content, pos + 1, ch)
+        } else if let Some(end) = rest.strip_prefix("<<<").and_then(|_| heredoc_end(content, pos + 3)) {
+            end
+        } else if is_ident_char(ch) {
+            pos + rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len())
+        } else {
+            pos + ch.len_utf8()
+        };
+    }
+
+    content.len()
+}
+
+// the end of a string whose body starts at `pos`; strings span lines, and the
+// {$...} interpolations in "..." may contain quotes
+fn string_end(content: &str, mut pos: usize, quote: char) -> usize {
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+        pos = match ch {
+            '\\' => pos + 1 + rest[1..].chars().next().map_or(0, char::len_utf8),
+            '{' if quote != '\'' && rest.starts_with("{$") => interpolation_end(content, pos + 1),
+            _ if ch == quote => return pos + 1,
+            _ => pos + ch.len_utf8(),
+        };
+    }
+    content.len()
+}
+
+// the end of a {$...} interpolation whose body starts at `pos`
+fn interpolation_end(content: &str, mut pos: usize) -> usize {
+    let mut depth = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        pos = match ch {
+            '}' if depth == 0 => return pos + 1,
+            '}' => {
+                depth -= 1;
+                pos + 1
+            },
+            '{' => {
+                depth += 1;
+                pos + 1
+            },
+            '\'' | '"' => string_end(content, pos + 1, ch),
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    content.len()
+}
+
+// <<<EOT, <<<"EOT" heredocs and <<<'EOT' nowdocs; `pos` is just past the `<<<`.
+// The closing identifier may be indented and followed by `;`, `,` or `)`
+fn heredoc_end(content: &str, pos: usize) -> Option<usize> {
+    let rest = content[pos..].trim_start_matches([' ', '\t']);
+    let quote = rest.chars().next().filter(|&c| c == '\'' || c == '"');
+    let name = &rest[usize::from(quote.is_some())..];
+    let name = &name[..name.find(|c: char| !is_ident_char(c)).unwrap_or(name.len())];
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut line_start = (line_end(content, pos) + 1).min(content.len());
+    while line_start < content.len() {
+        let end = line_end(content, line_start);
+        let line = content[line_start..end].trim_start();
+        if line.strip_prefix(name).is_some_and(|after| !after.starts_with(is_ident_char)) {
+            return Some(end - line.len() + name.len());
+        }
+        line_start = end + 1;
+    }
+    Some(content.len())
+}
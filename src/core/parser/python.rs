@@ -0,0 +1,174 @@
+use super::scan::{hash_comment, is_ident_char, skip};
+use super::{CommentMatch, CommentType};
+
+enum PythonParseState {
+    Code,
+    SingleLineComment,
+    StringLiteral(char), // the quote that closes it
+    TripleQuotedString(char),
+}
+
+// `#` comments plus docstrings: triple-quoted strings that are the first
+// statement of a module, class or function. Other strings are skipped
+pub fn extract_python_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = PythonParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    let mut docstring_start: Option<usize> = None; // set while reading a docstring
+
+    let mut depth: usize = 0; // open brackets, newlines inside them don't end a statement
+    let mut statement_start = true; // no token of the current statement seen yet
+    let mut in_header = false; // in a def or class line, up to its `:`
+    let mut docstring_expected = true; // the next statement may be a docstring
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            PythonParseState::Code => {
+                // string prefixes like r"" and f"" are read as part of the string
+                let mut quote = None;
+                let mut prefix = "";
+                if ch == '"' || ch == '\'' {
+                    quote = Some(ch);
+                } else if is_ident_char(ch) {
+                    let mut word_end = byte_pos + ch.len_utf8();
+                    while let Some((pos, next)) = chars.next_if(|&(_, next)| is_ident_char(next)) {
+                        word_end = pos + next.len_utf8();
+                    }
+                    let word = &content[byte_pos..word_end];
+
+                    if is_string_prefix(word)
+                        && let Some((_, next)) = chars.next_if(|&(_, next)| next == '"' || next == '\'')
+                    {
+                        quote = Some(next);
+                        prefix = word;
+                    } else {
+                        if statement_start && (word == "def" || word == "class") {
+                            in_header = true;
+                        }
+                        // `async def`
+                        statement_start = statement_start && word == "async";
+                        docstring_expected = false;
+                        continue;
+                    }
+                }
+
+                if let Some(quote) = quote {
+                    let quote_pos = byte_pos + prefix.len();
+                    let is_docstring = statement_start && docstring_expected
+                        && !prefix.to_ascii_lowercase().contains(['b', 'f', 't']);
+
+                    if content[quote_pos..].starts_with(triple_quote(quote)) {
+                        skip(&mut chars, 2);
+                        if is_docstring {
+                            docstring_start = Some(byte_pos);
+                        }
+                        state = PythonParseState::TripleQuotedString(quote);
+                    } else {
+                        state = PythonParseState::StringLiteral(quote);
+                    }
+                    statement_start = false;
+                    docstring_expected = false;
+                    continue;
+                }
+
+                match ch {
+                    '#' => {
+                        comment_start = Some(byte_pos);
+                        state = PythonParseState::SingleLineComment;
+                    },
+                    // a line continuation
+                    '\\' => { chars.next_if(|&(_, next)| next == '\n'); },
+                    '\n' => {
+                        if depth == 0 {
+                            statement_start = true;
+                        }
+                    },
+                    '(' | '[' | '{' => {
+                        depth += 1;
+                        statement_start = false;
+                        docstring_expected = false;
+                    },
+                    ')' | ']' | '}' => {
+                        depth = depth.saturating_sub(1);
+                    },
+                    // the body of a def or class starts after its `:`, maybe on the same line
+                    ':' if depth == 0 && in_header => {
+                        in_header = false;
+                        statement_start = true;
+                        docstring_expected = true;
+                    },
+                    ';' if depth == 0 => {
+                        statement_start = true;
+                    },
+                    _ if ch.is_whitespace() => {},
+                    _ => {
+                        statement_start = false;
+                        docstring_expected = false;
+                    }
+                }
+            },
+            PythonParseState::SingleLineComment => {
+                if ch == '\n' {
+                    // a #! first line is a directive
+                    if let Some(start) = comment_start {
+                        comments.push(hash_comment(content, start, byte_pos));
+                    }
+                    comment_start = None;
+                    state = PythonParseState::Code;
+                    if depth == 0 {
+                        statement_start = true;
+                    }
+                }
+            },
+            PythonParseState::StringLiteral(quote) => {
+                match ch {
+                    '\\' => { chars.next(); }, // Skip escaped character
+                    // unterminated, the string ends with the line
+                    '\n' => {
+                        state = PythonParseState::Code;
+                        if depth == 0 {
+                            statement_start = true;
+                        }
+                    },
+                    _ if ch == quote => state = PythonParseState::Code,
+                    _ => {}
+                }
+            },
+            PythonParseState::TripleQuotedString(quote) => {
+                if ch == '\\' {
+                    chars.next();
+                } else if content[byte_pos..].starts_with(triple_quote(quote)) {
+                    skip(&mut chars, 2);
+                    if let Some(start) = docstring_start.take() {
+                        comments.push(CommentMatch {
+                            start_byte: start,
+                            end_byte: byte_pos + 3,
+                            comment_type: CommentType::Docstring,
+                        });
+                    }
+                    state = PythonParseState::Code;
+                }
+            }
+        }
+    }
+
+    // Handle comment at end of file
+    if let Some(start) = docstring_start {
+        comments.push(CommentMatch { start_byte: start, end_byte: content.len(), comment_type: CommentType::Docstring });
+    } else if let Some(start) = comment_start {
+        comments.push(hash_comment(content, start, content.len()));
+    }
+
+    comments
+}
+
+fn triple_quote(quote: char) -> &'static str {
+    if quote == '"' { "\"\"\"" } else { "'''" }
+}
+
+// r, u, f, b, t and their combinations like rb or Rf
+fn is_string_prefix(word: &str) -> bool {
+    word.len() <= 2 && word.chars().all(|c| matches!(c.to_ascii_lowercase(), 'r' | 'u' | 'f' | 'b' | 't'))
+}
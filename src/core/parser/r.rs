@@ -0,0 +1,65 @@
+use super::scan::{closing_bracket, delimited_end, hash_comment, is_ident_char, skip_to};
+use super::CommentMatch;
+
+enum RParseState {
+    Code,
+    LineComment,
+}
+
+pub fn extract_r_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = RParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    let mut prev_ch = '\n'; // last char seen in code
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            RParseState::Code => {
+                match ch {
+                    '#' => {
+                        comment_start = Some(byte_pos);
+                        state = RParseState::LineComment;
+                    },
+                    // strings and `non-syntactic names`
+                    '"' | '\'' | '`' => skip_to(&mut chars, delimited_end(content, byte_pos + 1, ch)),
+                    'r' | 'R' if !is_ident_char(prev_ch) && prev_ch != '.' => {
+                        if let Some(end) = raw_string_end(content, byte_pos + 1) {
+                            skip_to(&mut chars, end);
+                        }
+                    },
+                    _ => {}
+                }
+                prev_ch = ch;
+            },
+            RParseState::LineComment => {
+                if ch == '\n' {
+                    comments.push(hash_comment(content, comment_start.take().unwrap(), byte_pos));
+                    state = RParseState::Code;
+                    prev_ch = ch;
+                }
+            }
+        }
+    }
+
+    if let (RParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(hash_comment(content, start, content.len()));
+    }
+
+    comments
+}
+
+// r"(...)", R'[...]', r"---{...}---"; `pos` is just past the `r`
+fn raw_string_end(content: &str, pos: usize) -> Option<usize> {
+    let rest = &content[pos..];
+    let quote = rest.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let dashes = rest[1..].len() - rest[1..].trim_start_matches('-').len();
+    let open = rest[1 + dashes..].chars().next()?;
+    let close = closing_bracket(open).filter(|_| open != '<')?;
+
+    let terminator = format!("{}{}{}", close, &rest[1..1 + dashes], quote);
+    let body_start = pos + 2 + dashes;
+    let end = content[body_start..].find(&terminator).map_or(content.len(), |i| body_start + i + terminator.len());
+    Some(end)
+}
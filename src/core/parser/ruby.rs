@@ -0,0 +1,154 @@
+use super::scan::{
+    delimited_end, hash_comment, heredoc_delimiter, interpolated_end, is_ident_char,
+    line_end, skip_heredocs, skip_to,
+};
+use super::{CommentMatch, CommentType};
+
+enum RubyParseState {
+    Code,
+    LineComment,
+}
+
+pub fn extract_ruby_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = RubyParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    let mut prev_ch = '\n'; // last char seen in code
+    let mut last_token = '\n'; // last non-whitespace char seen in code
+    let mut heredocs: Vec<(&str, bool)> = Vec::new(); // opened on this line, bodies start on the next
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            RubyParseState::Code => {
+                // after a value `/` and `%` are operators, otherwise they start literals
+                let after_value = is_ident_char(last_token) || matches!(last_token, ')' | ']' | '}' | '"' | '\'' | '`');
+                // `puts /x/` and `puts %w[a]` pass a literal to a method called without parens
+                let argument_start = prev_ch.is_whitespace()
+                    && chars.peek().is_some_and(|&(_, next)| !next.is_whitespace() && next != '=');
+                let starts_literal = !after_value || argument_start;
+
+                match ch {
+                    '#' => {
+                        comment_start = Some(byte_pos);
+                        state = RubyParseState::LineComment;
+                        continue;
+                    },
+                    '=' if prev_ch == '\n' => {
+                        if let Some(end) = embedded_document_end(content, byte_pos) {
+                            comments.push(CommentMatch {
+                                start_byte: byte_pos,
+                                end_byte: end,
+                                comment_type: CommentType::MultiLine,
+                            });
+                            skip_to(&mut chars, end);
+                            continue;
+                        }
+                    },
+                    // everything after __END__ is data
+                    '_' if prev_ch == '\n' && content[byte_pos..line_end(content, byte_pos)] == *"__END__" => break,
+                    '"' | '`' => skip_to(&mut chars, interpolated_end(content, byte_pos + 1, ch)),
+                    '\'' => skip_to(&mut chars, delimited_end(content, byte_pos + 1, ch)),
+                    '%' if starts_literal => {
+                        if let Some(end) = percent_literal_end(content, byte_pos) {
+                            skip_to(&mut chars, end);
+                        }
+                    },
+                    '/' if starts_literal => {
+                        // only single-line regexes, so a misread division can't swallow the file
+                        let line = &content[..line_end(content, byte_pos)];
+                        let end = interpolated_end(line, byte_pos + 1, '/');
+                        if line[..end].ends_with('/') && end > byte_pos + 1 {
+                            skip_to(&mut chars, end);
+                        }
+                    },
+                    '?' if !after_value => { chars.next_if(|&(_, next)| !next.is_whitespace()); }, // ?a, ?#
+                    '$' => { chars.next_if(|&(_, next)| next.is_ascii_punctuation() && next != '{'); }, // $' $" $`
+                    '<' if content[byte_pos..].starts_with("<<") && starts_literal => {
+                        if let Some((heredoc, end)) = parse_heredoc(content, byte_pos + 2) {
+                            heredocs.push(heredoc);
+                            skip_to(&mut chars, end);
+                        }
+                    },
+                    '\n' => skip_heredocs(content, byte_pos + 1, &mut heredocs, &mut chars),
+                    _ => {}
+                }
+
+                prev_ch = ch;
+                if !ch.is_whitespace() {
+                    last_token = ch;
+                }
+            },
+            RubyParseState::LineComment => {
+                if ch == '\n' {
+                    comments.push(hash_comment(content, comment_start.take().unwrap(), byte_pos));
+                    skip_heredocs(content, byte_pos + 1, &mut heredocs, &mut chars);
+                    state = RubyParseState::Code;
+                    prev_ch = ch;
+                    last_token = ch;
+                }
+            }
+        }
+    }
+
+    if let (RubyParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(hash_comment(content, start, content.len()));
+    }
+
+    comments
+}
+
+// for a line starting with `=begin`, the end of the matching `=end` line
+fn embedded_document_end(content: &str, pos: usize) -> Option<usize> {
+    let is_marker = |line: &str, marker: &str| {
+        line.strip_prefix(marker).is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+    };
+
+    if !is_marker(&content[pos..line_end(content, pos)], "=begin") {
+        return None;
+    }
+
+    let mut line_start = pos;
+    loop {
+        let end = line_end(content, line_start);
+        if (line_start > pos && is_marker(&content[line_start..end], "=end")) || end == content.len() {
+            return Some(end);
+        }
+        line_start = end + 1;
+    }
+}
+
+// %q(...), %w[...], %Q{...}, %(...), %r{...}; `pos` is at the `%`
+fn percent_literal_end(content: &str, pos: usize) -> Option<usize> {
+    let rest = &content[pos + 1..];
+    let (kind, kind_len) = match rest.chars().next()? {
+        kind @ ('q' | 'Q' | 'w' | 'W' | 'i' | 'I' | 'r' | 's' | 'x') => (kind, 1),
+        _ => ('Q', 0),
+    };
+    let open = rest[kind_len..].chars().next()?;
+    if open.is_alphanumeric() || open.is_whitespace() || open == '=' {
+        return None;
+    }
+
+    let body_start = pos + 1 + kind_len + open.len_utf8();
+    Some(match kind {
+        'q' | 'w' | 'i' | 's' => delimited_end(content, body_start, open),
+        _ => interpolated_end(content, body_start, open),
+    })
+}
+
+// <<~EOS, <<-EOS, <<EOS, <<~'EOS'; `pos` is just past the `<<`. A bare
+// identifier must be uppercase so `x <<y` stays a shift
+fn parse_heredoc(content: &str, pos: usize) -> Option<((&str, bool), usize)> {
+    let rest = &content[pos..];
+    let indented = rest.starts_with(['~', '-']);
+    let (delimiter, len) = heredoc_delimiter(&rest[usize::from(indented)..])?;
+
+    let quoted = len != delimiter.len();
+    if !quoted && !delimiter.starts_with(|c: char| c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    Some(((delimiter, indented), pos + usize::from(indented) + len))
+}
@@ -0,0 +1,139 @@
+use super::scan::{is_ident_char, skip, Chars};
+use super::{CommentMatch, CommentType};
+
+enum RustParseState {
+    Code,
+    LineComment,
+    BlockComment(usize), // nesting depth, Rust block comments nest
+    StringLiteral,
+    RawString(usize), // number of `#` around the raw string
+}
+
+pub fn extract_rust_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = RustParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    let mut prev_ch = ' '; // last char seen in code, to tell `r"` apart from `bar"`
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            RustParseState::Code => {
+                match ch {
+                    '/' => {
+                        match chars.peek() {
+                            Some(&(_, '/')) => {
+                                comment_start = Some(byte_pos);
+                                state = RustParseState::LineComment;
+                                chars.next();
+                            },
+                            Some(&(_, '*')) => {
+                                comment_start = Some(byte_pos);
+                                state = RustParseState::BlockComment(1);
+                                chars.next();
+                            },
+                            _ => {}
+                        }
+                    },
+                    '"' => { state = RustParseState::StringLiteral },
+                    // r"..", r#".."#, br"..", cr".."; but not r#ident
+                    'r' if !is_ident_char(prev_ch) || matches!(prev_ch, 'b' | 'c') => {
+                        let rest = &content[byte_pos + 1..];
+                        let hashes = rest.len() - rest.trim_start_matches('#').len();
+                        if rest[hashes..].starts_with('"') {
+                            skip(&mut chars, hashes + 1);
+                            state = RustParseState::RawString(hashes);
+                        }
+                    },
+                    '\'' => skip_char_literal(content, byte_pos, &mut chars),
+                    _ => {}
+                }
+                prev_ch = ch;
+            },
+            RustParseState::LineComment => {
+                if ch == '\n' {
+                    comments.push(CommentMatch {
+                        start_byte: comment_start.take().unwrap(),
+                        end_byte: byte_pos,
+                        comment_type: CommentType::SingleLine,
+                    });
+                    state = RustParseState::Code;
+                }
+            },
+            RustParseState::BlockComment(depth) => {
+                match (ch, chars.peek()) {
+                    ('/', Some(&(_, '*'))) => {
+                        chars.next();
+                        state = RustParseState::BlockComment(depth + 1);
+                    },
+                    ('*', Some(&(end, '/'))) => {
+                        chars.next();
+                        if depth == 1 {
+                            comments.push(CommentMatch {
+                                start_byte: comment_start.take().unwrap(),
+                                end_byte: end + 1,
+                                comment_type: CommentType::MultiLine,
+                            });
+                            state = RustParseState::Code;
+                            prev_ch = ' ';
+                        } else {
+                            state = RustParseState::BlockComment(depth - 1);
+                        }
+                    },
+                    _ => {}
+                }
+            },
+            RustParseState::StringLiteral => {
+                match ch {
+                    '\\' => { chars.next(); },
+                    '"' => {
+                        state = RustParseState::Code;
+                        prev_ch = ch;
+                    },
+                    _ => {}
+                }
+            },
+            RustParseState::RawString(hashes) => {
+                if ch == '"' {
+                    let rest = &content[byte_pos + 1..];
+                    let closing = rest.len() - rest.trim_start_matches('#').len();
+                    if closing >= hashes {
+                        skip(&mut chars, hashes);
+                        state = RustParseState::Code;
+                        prev_ch = ch;
+                    }
+                }
+            }
+        }
+    }
+
+    // line comment on the last line without a trailing newline
+    if let (RustParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(CommentMatch {
+            start_byte: start,
+            end_byte: content.len(),
+            comment_type: CommentType::SingleLine,
+        });
+    }
+
+    comments
+}
+
+// `'a'`, `'\n'`, `'\u{1F600}'` and `b'x'` are char literals, `'a` and `'static`
+// are lifetimes or labels and stay in code
+fn skip_char_literal(content: &str, quote_pos: usize, chars: &mut Chars) {
+    let mut rest = content[quote_pos + 1..].chars();
+    match (rest.next(), rest.next()) {
+        (Some('\\'), Some(_)) => {
+            skip(chars, 2); // the backslash and the escaped char
+            for (_, ch) in chars.by_ref() {
+                if ch == '\'' || ch == '\n' {
+                    break;
+                }
+            }
+        },
+        (Some(_), Some('\'')) => skip(chars, 2),
+        _ => {}
+    }
+}
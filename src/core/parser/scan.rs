@@ -0,0 +1,212 @@
+// small helpers shared by the hand-written extractors
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use super::{CommentMatch, CommentType};
+
+pub type Chars<'a> = Peekable<CharIndices<'a>>;
+
+pub fn skip(chars: &mut Chars, count: usize) {
+    for _ in 0..count {
+        chars.next();
+    }
+}
+
+// advances until the next char starts at or after `byte_pos`
+pub fn skip_to(chars: &mut Chars, byte_pos: usize) {
+    while chars.next_if(|&(pos, _)| pos < byte_pos).is_some() {}
+}
+
+pub fn is_ident_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+// byte position of the end of the line containing `pos` (the `\n` or EOF)
+pub fn line_end(content: &str, pos: usize) -> usize {
+    content[pos..].find('\n').map_or(content.len(), |i| pos + i)
+}
+
+// byte position of the start of the line after the one containing `pos`
+pub fn next_line_start(content: &str, pos: usize) -> usize {
+    (line_end(content, pos) + 1).min(content.len())
+}
+
+// the closing bracket for a nesting literal delimiter, e.g. the `{` of Ruby's `%q{...}`
+pub fn closing_bracket(open: char) -> Option<char> {
+    match open {
+        '(' => Some(')'),
+        '[' => Some(']'),
+        '{' => Some('}'),
+        '<' => Some('>'),
+        _ => None,
+    }
+}
+
+// byte position just past a literal whose body starts at `body_start` and that
+// was opened with `open`: brackets nest, other delimiters end at their next
+// unescaped occurrence. Unterminated literals run to the end of `content`
+pub fn delimited_end(content: &str, body_start: usize, open: char) -> usize {
+    let close = closing_bracket(open).unwrap_or(open);
+    let mut depth = 0;
+    let mut chars = content[body_start..].char_indices();
+
+    while let Some((i, ch)) = chars.next() {
+        if ch == '\\' {
+            chars.next();
+        } else if ch == close && depth == 0 {
+            return body_start + i + ch.len_utf8();
+        } else if ch == close {
+            depth -= 1;
+        } else if ch == open && open != close {
+            depth += 1;
+        }
+    }
+
+    content.len()
+}
+
+// like delimited_end, but skips the `#{...}` interpolations of Ruby and
+// Elixir, which may contain the delimiter: "a #{h["k"]} b"
+pub fn interpolated_end(content: &str, body_start: usize, open: char) -> usize {
+    let close = closing_bracket(open).unwrap_or(open);
+    let mut depth = 0;
+    let mut pos = body_start;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let next = pos + ch.len_utf8();
+        pos = match ch {
+            '\\' => next + content[next..].chars().next().map_or(0, char::len_utf8),
+            '#' if content[next..].starts_with('{') => interpolation_end(content, next + 1),
+            _ if ch == close && depth == 0 => return next,
+            _ if ch == close => {
+                depth -= 1;
+                next
+            },
+            _ if ch == open && open != close => {
+                depth += 1;
+                next
+            },
+            _ => next,
+        };
+    }
+
+    content.len()
+}
+
+// the end of the code in `#{...}`, whose body starts at `body_start`
+fn interpolation_end(content: &str, body_start: usize) -> usize {
+    let mut depth = 0;
+    let mut pos = body_start;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let next = pos + ch.len_utf8();
+        pos = match ch {
+            '}' if depth == 0 => return next,
+            '}' => {
+                depth -= 1;
+                next
+            },
+            '{' => {
+                depth += 1;
+                next
+            },
+            '"' | '`' => interpolated_end(content, next, ch),
+            '\'' => delimited_end(content, next, ch),
+            _ => next,
+        };
+    }
+
+    content.len()
+}
+
+// byte position just past the terminator line of a heredoc whose body starts
+// at `body_start`. With `indented` the terminator may be indented (`<<-`, `<<~`)
+pub fn heredoc_end(content: &str, body_start: usize, delimiter: &str, indented: bool) -> usize {
+    let mut pos = body_start;
+    while pos < content.len() {
+        let end = line_end(content, pos);
+        let line = &content[pos..end];
+        let line = if indented { line.trim_start() } else { line };
+        if line == delimiter {
+            return (end + 1).min(content.len());
+        }
+        pos = end + 1;
+    }
+    content.len()
+}
+
+// heredoc bodies aren't code, skips them once the line that opened them ends
+pub fn skip_heredocs(content: &str, body_start: usize, heredocs: &mut Vec<(&str, bool)>, chars: &mut Chars) {
+    let mut pos = body_start;
+    for (delimiter, indented) in heredocs.drain(..) {
+        pos = heredoc_end(content, pos, delimiter, indented);
+    }
+    skip_to(chars, pos);
+}
+
+// the delimiter of a heredoc right after `<<` (and any `-`/`~`): EOF, 'EOF',
+// "EOF" or \EOF, together with the length of its spelling
+pub fn heredoc_delimiter(rest: &str) -> Option<(&str, usize)> {
+    if let Some(quote) = rest.chars().next().filter(|&c| c == '\'' || c == '"') {
+        let len = rest[1..].find([quote, '\n'])?;
+        let delimiter = &rest[1..1 + len];
+        return (!delimiter.is_empty() && rest[1 + len..].starts_with(quote)).then_some((delimiter, len + 2));
+    }
+
+    let escaped = usize::from(rest.starts_with('\\'));
+    let word = &rest[escaped..];
+    let len = word.find(|c: char| !is_ident_char(c)).unwrap_or(word.len());
+    (len > 0).then_some((&word[..len], len + escaped))
+}
+
+// a `#` line comment; the `#!` line at the top of a script is a directive
+pub fn hash_comment(content: &str, start: usize, end: usize) -> CommentMatch {
+    let comment_type = if start == 0 && content.starts_with("#!") {
+        CommentType::Directive
+    } else {
+        CommentType::SingleLine
+    };
+
+    CommentMatch { start_byte: start, end_byte: end, comment_type }
+}
+
+// the end of a block comment starting at `start` whose delimiters nest, as in
+// /* a /* b */ c */; an unterminated one runs to the end of `content`
+pub fn nested_comment_end(content: &str, start: usize, open: &str, close: &str) -> usize {
+    let first_chars: Vec<char> = open.chars().take(1).chain(close.chars().take(1)).collect();
+    let mut depth = 0;
+    let mut pos = start;
+
+    while let Some(offset) = content[pos..].find(first_chars.as_slice()) {
+        let at = pos + offset;
+        if content[at..].starts_with(open) {
+            depth += 1;
+            pos = at + open.len();
+        } else if content[at..].starts_with(close) {
+            depth -= 1;
+            pos = at + close.len();
+            if depth == 0 {
+                return pos;
+            }
+        } else {
+            pos = at + 1;
+        }
+    }
+
+    content.len()
+}
+
+// the end of a char literal like 'a', '\n' or '\u0041' whose quote is at
+// `quote_pos`; None for a quote that starts something else ('T, 'sym)
+pub fn char_literal_end(content: &str, quote_pos: usize) -> Option<usize> {
+    let body = quote_pos + 1;
+    let mut rest = content[body..].char_indices();
+    match (rest.next(), rest.next()) {
+        (Some((_, '\\')), Some((i, escaped))) => {
+            let escape_end = body + i + escaped.len_utf8();
+            content[escape_end..line_end(content, escape_end)].find('\'').map(|j| escape_end + j + 1)
+        },
+        (Some(_), Some((i, '\''))) => Some(body + i + 1),
+        _ => None,
+    }
+}
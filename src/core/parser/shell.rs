@@ -0,0 +1,101 @@
+// sh, bash, zsh and ksh
+use super::scan::{hash_comment, heredoc_delimiter, skip_heredocs, skip_to};
+use super::CommentMatch;
+
+enum ShellParseState {
+    Code,
+    LineComment,
+    SingleQuoted, // '...', no escapes
+    DoubleQuoted,
+    AnsiCString, // $'...', backslash escapes like "..."
+    Backtick,
+}
+
+pub fn extract_shell_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = ShellParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    let mut prev_ch = '\n'; // last char seen in code
+    let mut heredocs: Vec<(&str, bool)> = Vec::new(); // opened on this line, bodies start on the next
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            ShellParseState::Code => {
+                match ch {
+                    // only at the start of a word: `a#b`, `$#` and `${#x}` aren't comments
+                    '#' if prev_ch.is_whitespace() || matches!(prev_ch, ';' | '&' | '|' | '(' | ')') => {
+                        comment_start = Some(byte_pos);
+                        state = ShellParseState::LineComment;
+                    },
+                    '\\' => { chars.next(); },
+                    '\'' if prev_ch == '$' => { state = ShellParseState::AnsiCString },
+                    '\'' => { state = ShellParseState::SingleQuoted },
+                    '"' => { state = ShellParseState::DoubleQuoted },
+                    '`' => { state = ShellParseState::Backtick },
+                    '<' if content[byte_pos..].starts_with("<<<") => skip_to(&mut chars, byte_pos + 3), // here-string
+                    '<' if content[byte_pos..].starts_with("<<") => {
+                        if let Some((heredoc, end)) = parse_heredoc(content, byte_pos + 2) {
+                            heredocs.push(heredoc);
+                            skip_to(&mut chars, end);
+                        }
+                    },
+                    '\n' => skip_heredocs(content, byte_pos + 1, &mut heredocs, &mut chars),
+                    _ => {}
+                }
+                prev_ch = ch;
+            },
+            ShellParseState::LineComment => {
+                if ch == '\n' {
+                    comments.push(hash_comment(content, comment_start.take().unwrap(), byte_pos));
+                    skip_heredocs(content, byte_pos + 1, &mut heredocs, &mut chars);
+                    state = ShellParseState::Code;
+                    prev_ch = ch;
+                }
+            },
+            ShellParseState::SingleQuoted => {
+                if ch == '\'' {
+                    state = ShellParseState::Code;
+                    prev_ch = ch;
+                }
+            },
+            ShellParseState::DoubleQuoted | ShellParseState::AnsiCString | ShellParseState::Backtick => {
+                let closing = match state {
+                    ShellParseState::DoubleQuoted => '"',
+                    ShellParseState::AnsiCString => '\'',
+                    _ => '`',
+                };
+                if ch == '\\' {
+                    chars.next();
+                } else if ch == closing {
+                    state = ShellParseState::Code;
+                    prev_ch = ch;
+                }
+            }
+        }
+    }
+
+    if let (ShellParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(hash_comment(content, start, content.len()));
+    }
+
+    comments
+}
+
+// `<<EOF`, `<<-EOF` (tab-indented terminator), `<< 'EOF'`; `pos` is just past
+// the `<<`. Returns the heredoc and where its opener ends
+pub fn parse_heredoc(content: &str, pos: usize) -> Option<((&str, bool), usize)> {
+    let rest = &content[pos..];
+    let indented = rest.starts_with('-');
+    let rest = &rest[usize::from(indented)..];
+    let spaces = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    let (delimiter, len) = heredoc_delimiter(&rest[spaces..])?;
+
+    // $((1<<2)) is a shift
+    if len == delimiter.len() && delimiter.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(((delimiter, indented), pos + usize::from(indented) + spaces + len))
+}
@@ -0,0 +1,296 @@
+// languages described by data instead of a hand-written extractor: a built-in
+// table plus the user's own definitions, see languages.toml for the format
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use super::{CommentMatch, CommentType};
+
+const BUILTIN_LANGUAGES: &str = include_str!("languages.toml");
+const LANGUAGES_FILE: &str = "languages.toml";
+
+static LANGUAGES: OnceLock<Vec<LanguageSpec>> = OnceLock::new();
+
+#[derive(Debug, Deserialize)]
+struct LanguageFile {
+    #[serde(default)]
+    language: Vec<LanguageDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LanguageDefinition {
+    pub name: String,
+    #[serde(default)]
+    extensions: Vec<String>,
+    #[serde(default)]
+    file_names: Vec<String>,
+    #[serde(default)]
+    line_comments: Vec<String>,
+    #[serde(default)]
+    block_comments: Vec<BlockComment>,
+    #[serde(default)]
+    strings: Vec<StringDelimiter>,
+    #[serde(default)]
+    raw_strings: Vec<RawStringDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BlockComment {
+    open: String,
+    close: String,
+    #[serde(default)]
+    nested: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StringDelimiter {
+    open: String,
+    close: Option<String>, // defaults to `open`
+    #[serde(default = "default_escape")]
+    escape: String, // "" for no escapes
+    #[serde(default)]
+    doubled: bool, // a doubled closing delimiter is an escaped one, as in SQL's 'it''s'
+    #[serde(default)]
+    multiline: bool,
+}
+
+fn default_escape() -> String {
+    "\\".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawStringDefinition {
+    open: String,  // regex
+    close: String, // $1, $2... are replaced by the groups captured by `open`
+}
+
+#[derive(Debug)]
+pub struct LanguageSpec {
+    pub definition: LanguageDefinition,
+    raw_strings: Vec<(Regex, String)>,
+    token_starts: Vec<char>, // first chars of all comment and string tokens
+}
+
+// two specs are the same language if they have the same name
+impl PartialEq for LanguageSpec {
+    fn eq(&self, other: &Self) -> bool {
+        self.definition.name == other.definition.name
+    }
+}
+
+impl Eq for LanguageSpec {}
+
+impl LanguageSpec {
+    fn new(mut definition: LanguageDefinition) -> Result<LanguageSpec, String> {
+        definition.name = definition.name.trim().to_lowercase();
+        if definition.name.is_empty() {
+            return Err("language without a name".to_string());
+        }
+
+        let raw_strings = definition
+            .raw_strings
+            .iter()
+            .map(|raw| match Regex::new(&raw.open) {
+                Ok(open) => Ok((open, raw.close.clone())),
+                Err(e) => Err(format!("{}: invalid raw string pattern: {}", definition.name, e)),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // longer tokens first, so `#[` wins over `#` and `"""` over `"`
+        definition.block_comments.sort_by_key(|block| std::cmp::Reverse(block.open.len()));
+        definition.line_comments.sort_by_key(|token| std::cmp::Reverse(token.len()));
+        definition.strings.sort_by_key(|string| std::cmp::Reverse(string.open.len()));
+
+        let tokens = definition.block_comments.iter().map(|block| &block.open)
+            .chain(&definition.line_comments)
+            .chain(definition.strings.iter().map(|string| &string.open));
+        let mut token_starts = Vec::new();
+        for token in tokens {
+            let Some(first) = token.chars().next() else {
+                return Err(format!("{}: empty comment or string delimiter", definition.name));
+            };
+            if !token_starts.contains(&first) {
+                token_starts.push(first);
+            }
+        }
+
+        Ok(LanguageSpec { definition, raw_strings, token_starts })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.definition.name
+    }
+
+    pub fn matches_extension(&self, extension: &str) -> bool {
+        self.definition.extensions.iter().any(|e| e == extension)
+    }
+
+    pub fn matches_file_name(&self, file_name: &str) -> bool {
+        self.definition.file_names.iter().any(|name| name == file_name)
+    }
+}
+
+// every data-driven language, the user's definitions first so they can take
+// over extensions from the built-in ones. Falls back to the built-in table
+// if load_languages wasn't called
+pub fn languages() -> &'static [LanguageSpec] {
+    LANGUAGES.get_or_init(|| parse_languages(BUILTIN_LANGUAGES).expect("invalid built-in language table"))
+}
+
+// reads languages.toml from the project's `config_dir` and from the user's
+// config directory, project definitions win
+pub fn load_languages(config_dir: &Path) -> Result<(), String> {
+    let mut specs = Vec::new();
+
+    for path in [Some(config_dir.join(LANGUAGES_FILE)), user_languages_file()].into_iter().flatten() {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let parsed = parse_languages(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        specs.extend(parsed);
+    }
+    specs.extend(parse_languages(BUILTIN_LANGUAGES).expect("invalid built-in language table"));
+
+    LANGUAGES.set(specs).map_err(|_| "languages already loaded".to_string())
+}
+
+fn parse_languages(source: &str) -> Result<Vec<LanguageSpec>, String> {
+    let file: LanguageFile = toml::from_str(source).map_err(|e| e.to_string())?;
+    file.language.into_iter().map(LanguageSpec::new).collect()
+}
+
+// $XDG_CONFIG_HOME/fuzc/languages.toml, ~/.config/fuzc/languages.toml or
+// %APPDATA%\fuzc\languages.toml
+fn user_languages_file() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(config_dir.join("fuzc").join(LANGUAGES_FILE))
+}
+
+pub fn extract_spec_comments(content: &str, spec: &LanguageSpec) -> Vec<CommentMatch> {
+    let definition = &spec.definition;
+    let mut comments = Vec::new();
+    let mut pos = 0;
+    // where each raw string pattern matches next, refreshed once `pos` passes it
+    let mut next_raw: Vec<Option<usize>> = spec.raw_strings.iter()
+        .map(|(open, _)| open.find(content).map(|m| m.start()))
+        .collect();
+
+    loop {
+        for (next, (open, _)) in next_raw.iter_mut().zip(&spec.raw_strings) {
+            if next.is_some_and(|start| start < pos) {
+                *next = open.find_at(content, pos).map(|m| m.start());
+            }
+        }
+
+        let next_token = content[pos..].find(spec.token_starts.as_slice()).map(|offset| pos + offset);
+        let next_raw_start = next_raw.iter().flatten().min().copied();
+        let Some(at) = next_token.into_iter().chain(next_raw_start).min() else {
+            break;
+        };
+        let rest = &content[at..];
+
+        if next_raw_start == Some(at)
+            && let Some(end) = raw_string_end(content, at, spec)
+        {
+            pos = end;
+        } else if let Some(block) = definition.block_comments.iter().find(|block| rest.starts_with(&block.open)) {
+            let end = block_comment_end(content, at, block);
+            comments.push(CommentMatch { start_byte: at, end_byte: end, comment_type: CommentType::MultiLine });
+            pos = end;
+        } else if definition.line_comments.iter().any(|token| rest.starts_with(token.as_str())) {
+            let end = rest.find('\n').map_or(content.len(), |i| at + i);
+            comments.push(CommentMatch { start_byte: at, end_byte: end, comment_type: CommentType::SingleLine });
+            pos = end;
+        } else if let Some(string) = definition.strings.iter().find(|string| rest.starts_with(&string.open)) {
+            pos = string_end(content, at, string);
+        } else {
+            pos = at + rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+
+    comments
+}
+
+// None if no raw string pattern matches exactly at `start`
+fn raw_string_end(content: &str, start: usize, spec: &LanguageSpec) -> Option<usize> {
+    spec.raw_strings.iter().find_map(|(open, close)| {
+        let captures = open.captures_at(content, start)?;
+        let opener = captures.get(0)?;
+        if opener.start() != start {
+            return None;
+        }
+
+        let mut terminator = String::new();
+        captures.expand(close, &mut terminator);
+        let end = content[opener.end()..]
+            .find(&terminator)
+            .map_or(content.len(), |i| opener.end() + i + terminator.len());
+        // an empty match must still move the scan forward
+        Some(end.max(start + 1).min(content.len()))
+    })
+}
+
+fn block_comment_end(content: &str, start: usize, block: &BlockComment) -> usize {
+    let mut depth = 1;
+    let mut pos = start + block.open.len();
+
+    loop {
+        let rest = &content[pos..];
+        let Some(close) = rest.find(&block.close) else {
+            return content.len();
+        };
+        match rest.find(&block.open).filter(|_| block.nested) {
+            Some(open) if open < close => {
+                depth += 1;
+                pos += open + block.open.len();
+            },
+            _ => {
+                depth -= 1;
+                pos += close + block.close.len();
+                if depth == 0 {
+                    return pos;
+                }
+            }
+        }
+    }
+}
+
+fn string_end(content: &str, start: usize, string: &StringDelimiter) -> usize {
+    let close = string.close.as_deref().unwrap_or(&string.open);
+    let escape = string.escape.chars().next();
+    let mut pos = start + string.open.len();
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+        if !close.is_empty() && rest.starts_with(close) {
+            if string.doubled && rest[close.len()..].starts_with(close) {
+                pos += 2 * close.len();
+                continue;
+            }
+            return pos + close.len();
+        }
+
+        if Some(ch) == escape {
+            pos += ch.len_utf8();
+            pos += content[pos..].chars().next().map_or(0, char::len_utf8);
+        } else if ch == '\n' && !string.multiline {
+            return pos;
+        } else {
+            pos += ch.len_utf8();
+        }
+    }
+
+    content.len()
+}
@@ -0,0 +1,126 @@
+// one extractor for the common dialects: standard `--` and `/* */` comments
+// plus MySQL's `#` comments and `/*! */` executable comments
+use super::scan::{is_ident_char, skip_to};
+use super::{CommentMatch, CommentType};
+
+enum SqlParseState {
+    Code,
+    LineComment,
+    StringLiteral(bool), // whether backslash escapes apply, as in E'...'
+    QuotedIdentifier(char), // "name", `name` or [name], closed by this char
+}
+
+pub fn extract_sql_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut state = SqlParseState::Code;
+    let mut chars = content.char_indices().peekable();
+
+    let mut comment_start: Option<usize> = None;
+    let mut prev_ch = '\n'; // last char seen in code
+
+    while let Some((byte_pos, ch)) = chars.next() {
+        match state {
+            SqlParseState::Code => {
+                match ch {
+                    '-' if chars.next_if(|&(_, next)| next == '-').is_some() => {
+                        comment_start = Some(byte_pos);
+                        state = SqlParseState::LineComment;
+                    },
+                    // MySQL comment; elsewhere `#` is an operator (PostgreSQL's #> and a # b)
+                    '#' if prev_ch == '\n' || chars.peek().is_none_or(|&(_, next)| next == ' ' || next == '\t' || next == '\n' || next == '#') => {
+                        comment_start = Some(byte_pos);
+                        state = SqlParseState::LineComment;
+                    },
+                    '/' if content[byte_pos..].starts_with("/*") => {
+                        let end = block_comment_end(content, byte_pos);
+                        // /*! ... */ runs on MySQL, /*+ ... */ is an optimizer hint
+                        let comment_type = if content[byte_pos + 2..].starts_with(['!', '+']) {
+                            CommentType::Directive
+                        } else {
+                            CommentType::MultiLine
+                        };
+                        comments.push(CommentMatch { start_byte: byte_pos, end_byte: end, comment_type });
+                        skip_to(&mut chars, end);
+                        prev_ch = ' ';
+                        continue;
+                    },
+                    '\'' => {
+                        let escapes = content[..byte_pos]
+                            .strip_suffix(['e', 'E'])
+                            .is_some_and(|before| !before.ends_with(is_ident_char));
+                        state = SqlParseState::StringLiteral(escapes);
+                    },
+                    '"' | '`' => { state = SqlParseState::QuotedIdentifier(ch) },
+                    '[' => { state = SqlParseState::QuotedIdentifier(']') },
+                    _ => {}
+                }
+                if !ch.is_whitespace() || ch == '\n' {
+                    prev_ch = ch;
+                }
+            },
+            SqlParseState::LineComment => {
+                if ch == '\n' {
+                    comments.push(CommentMatch {
+                        start_byte: comment_start.take().unwrap(),
+                        end_byte: byte_pos,
+                        comment_type: CommentType::SingleLine,
+                    });
+                    state = SqlParseState::Code;
+                    prev_ch = ch;
+                }
+            },
+            SqlParseState::StringLiteral(escapes) => {
+                match ch {
+                    '\\' if escapes => { chars.next(); },
+                    // '' is an escaped quote
+                    '\'' if chars.next_if(|&(_, next)| next == '\'').is_none() => {
+                        state = SqlParseState::Code;
+                        prev_ch = ch;
+                    },
+                    _ => {}
+                }
+            },
+            SqlParseState::QuotedIdentifier(closing) => {
+                if ch == closing && chars.next_if(|&(_, next)| next == closing).is_none() {
+                    state = SqlParseState::Code;
+                    prev_ch = ch;
+                }
+            }
+        }
+    }
+
+    if let (SqlParseState::LineComment, Some(start)) = (state, comment_start) {
+        comments.push(CommentMatch {
+            start_byte: start,
+            end_byte: content.len(),
+            comment_type: CommentType::SingleLine,
+        });
+    }
+
+    comments
+}
+
+// block comments nest in standard SQL and PostgreSQL but not in MySQL, so an
+// unbalanced nested comment ends at the first `*/` instead of the end of the file
+fn block_comment_end(content: &str, start: usize) -> usize {
+    let mut depth = 0;
+    let mut pos = start;
+
+    while let Some(offset) = content[pos..].find(['/', '*']) {
+        let at = pos + offset;
+        if content[at..].starts_with("/*") {
+            depth += 1;
+            pos = at + 2;
+        } else if content[at..].starts_with("*/") {
+            depth -= 1;
+            pos = at + 2;
+            if depth == 0 {
+                return pos;
+            }
+        } else {
+            pos = at + 1;
+        }
+    }
+
+    content[start + 2..].find("*/").map_or(content.len(), |i| start + 2 + i + 2)
+}
@@ -0,0 +1,99 @@
+// `//` and `///` comments and nesting `/* */` blocks. Strings may be """multi-line"""
+// or #"raw"#, and hold \(...) interpolations that can contain strings themselves
+use super::scan::{hash_comment, line_end, nested_comment_end};
+use super::{CommentMatch, CommentType};
+
+pub fn extract_swift_comments(content: &str) -> Vec<CommentMatch> {
+    let mut comments = Vec::new();
+    let mut pos = 0;
+
+    // #!/usr/bin/swift
+    if content.starts_with("#!") {
+        pos = line_end(content, 0);
+        comments.push(hash_comment(content, 0, pos));
+    }
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+
+        pos = if rest.starts_with("//") {
+            let end = line_end(content, pos);
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::SingleLine });
+            end
+        } else if rest.starts_with("/*") {
+            let end = nested_comment_end(content, pos, "/*", "*/");
+            comments.push(CommentMatch { start_byte: pos, end_byte: end, comment_type: CommentType::MultiLine });
+            end
+        } else if ch == '"' || ch == '#' {
+            // `#` also starts #if, #available and friends
+            string_end(content, pos).unwrap_or(pos + 1)
+        } else {
+            pos + ch.len_utf8()
+        };
+    }
+
+    comments
+}
+
+// the end of the string starting at `start`, which is at its opening quote or
+// at the first `#` of a raw string. A raw string #"..."# ends at a quote followed
+// by as many `#`, and its escapes need them too: \#n, \#(x). None if there's no
+// string at `start`
+fn string_end(content: &str, start: usize) -> Option<usize> {
+    let hashes = content[start..].len() - content[start..].trim_start_matches('#').len();
+    let quote_start = start + hashes;
+    if !content[quote_start..].starts_with('"') {
+        return None;
+    }
+
+    let multiline = content[quote_start..].starts_with("\"\"\"");
+    let quotes = if multiline { "\"\"\"" } else { "\"" };
+    let closing = format!("{}{}", quotes, "#".repeat(hashes));
+    let escape = format!("\\{}", "#".repeat(hashes));
+    let mut pos = quote_start + quotes.len();
+
+    while let Some(ch) = content[pos..].chars().next() {
+        let rest = &content[pos..];
+        if rest.starts_with(&closing) {
+            return Some(pos + closing.len());
+        }
+
+        pos = match ch {
+            '\\' if rest.starts_with(&escape) => {
+                let escaped = pos + escape.len();
+                if content[escaped..].starts_with('(') {
+                    interpolation_end(content, escaped + 1)
+                } else {
+                    escaped + content[escaped..].chars().next().map_or(0, char::len_utf8)
+                }
+            },
+            '\n' if !multiline => return Some(pos),
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    Some(content.len())
+}
+
+// the end of a \(...) interpolation whose body starts at `pos`
+fn interpolation_end(content: &str, mut pos: usize) -> usize {
+    let mut depth = 0;
+
+    while let Some(ch) = content[pos..].chars().next() {
+        pos = match ch {
+            ')' if depth == 0 => return pos + 1,
+            ')' => {
+                depth -= 1;
+                pos + 1
+            },
+            '(' => {
+                depth += 1;
+                pos + 1
+            },
+            '"' | '#' => string_end(content, pos).unwrap_or(pos + 1),
+            _ => pos + ch.len_utf8(),
+        };
+    }
+
+    content.len()
+}
@@ -0,0 +1,112 @@
+// exact comment nodes from tree-sitter grammars, enabled by the `tree-sitter`
+// cargo feature. Languages without a grammar keep the built-in extractors
+use tree_sitter::{Node, Parser};
+
+use super::go::is_directive;
+use super::{CommentMatch, CommentType, Language};
+
+// None if there's no grammar for `language` or the file doesn't parse cleanly,
+// in which case the caller falls back to the built-in extractor
+pub fn extract_comments(content: &str, language: Language) -> Option<Vec<CommentMatch>> {
+    let grammars = match language {
+        Language::Java => vec![tree_sitter_java::LANGUAGE],
+        Language::Python => vec![tree_sitter_python::LANGUAGE],
+        Language::JavaScript => vec![tree_sitter_javascript::LANGUAGE],
+        // .ts and .tsx share a Language. TSX goes first: the TypeScript grammar can
+        // read JSX as comparisons and regexes without an error, while the TS-only
+        // `<T>value` casts do fail to parse as TSX
+        Language::TypeScript => vec![tree_sitter_typescript::LANGUAGE_TSX, tree_sitter_typescript::LANGUAGE_TYPESCRIPT],
+        Language::Rust => vec![tree_sitter_rust::LANGUAGE],
+        Language::C => vec![tree_sitter_c::LANGUAGE],
+        Language::Cpp => vec![tree_sitter_cpp::LANGUAGE],
+        Language::Go => vec![tree_sitter_go::LANGUAGE],
+        _ => return None,
+    };
+
+    let mut parser = Parser::new();
+    let tree = grammars.into_iter().find_map(|grammar| {
+        parser.set_language(&grammar.into()).ok()?;
+        parser.parse(content, None).filter(|tree| !tree.root_node().has_error())
+    })?;
+
+    let mut comments = Vec::new();
+    collect_comments(tree.root_node(), content, language, &mut comments);
+    Some(comments)
+}
+
+// comment nodes are named `comment`, `line_comment` or `block_comment`
+// depending on the grammar; their children (e.g. Rust doc markers) are skipped
+fn collect_comments(root: Node, content: &str, language: Language, comments: &mut Vec<CommentMatch>) {
+    let mut cursor = root.walk();
+    let mut descend = true;
+
+    loop {
+        let node = cursor.node();
+        let is_comment = descend && node.kind().ends_with("comment");
+        if is_comment {
+            comments.push(comment_match(content, node.start_byte(), node.end_byte(), language));
+        } else if descend && language == Language::Python && is_docstring(node, content) {
+            comments.push(CommentMatch {
+                start_byte: node.start_byte(),
+                end_byte: node.end_byte(),
+                comment_type: CommentType::Docstring,
+            });
+        }
+
+        if descend && !is_comment && cursor.goto_first_child() {
+            continue;
+        }
+        if cursor.goto_next_sibling() {
+            descend = true;
+            continue;
+        }
+        if !cursor.goto_parent() {
+            break;
+        }
+        descend = false;
+    }
+}
+
+// a triple-quoted string statement that comes first in a module, class or function body
+fn is_docstring(node: Node, content: &str) -> bool {
+    if node.kind() != "string" {
+        return false;
+    }
+    let Some(statement) = node.parent().filter(|parent| parent.kind() == "expression_statement" && parent.named_child_count() == 1) else {
+        return false;
+    };
+    let Some(body) = statement.parent() else {
+        return false;
+    };
+    let is_body = body.kind() == "module"
+        || body.kind() == "block" && body.parent().is_some_and(|def| matches!(def.kind(), "function_definition" | "class_definition"));
+
+    let mut cursor = body.walk();
+    let first = body.named_children(&mut cursor).find(|child| child.kind() != "comment");
+    let text = content[node.start_byte()..node.end_byte()].trim_start_matches(|c: char| c.is_ascii_alphabetic());
+    let prefix = &content[node.start_byte()..node.end_byte() - text.len()];
+
+    is_body
+        && first == Some(statement)
+        && (text.starts_with("\"\"\"") || text.starts_with("'''"))
+        && !prefix.to_ascii_lowercase().contains(['b', 'f', 't'])
+}
+
+fn comment_match(content: &str, start: usize, end: usize, language: Language) -> CommentMatch {
+    // some grammars include the line break that ends a line comment
+    let end = start + content[start..end].trim_end_matches(['\n', '\r']).len();
+    let text = &content[start..end];
+
+    let comment_type = if language == Language::Go && text.starts_with("//") && is_directive(text) {
+        CommentType::Directive
+    } else if start == 0 && text.starts_with("#!") {
+        // a Python script's shebang
+        CommentType::Directive
+    } else if text.starts_with("/*") {
+        CommentType::MultiLine
+    } else {
+        CommentType::SingleLine
+    };
+
+    CommentMatch { start_byte: start, end_byte: end, comment_type }
+}
@@ -0,0 +1,169 @@
+// formatting search results for CLI output
+use std::env;
+use std::io::{self, IsTerminal, Write};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use super::search::SearchResult;
+use super::tags::DocTag;
+
+// notebook locations name the cell, as in notebook.ipynb#cell:3:12
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Plain,       // file:line: text
+    Json,        // a single JSON array
+    Jsonl,       // one JSON object per line
+    Csv,
+    PathLine,    // path:line, for piping into other tools
+    PathLineCol, // path:line:column, e.g. for vim's quickfix list
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ColorChoice {
+    Auto, // color when stdout is a terminal and NO_COLOR isn't set
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                // https://no-color.org: any non-empty value disables color
+                let no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                !no_color && io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+// same scheme as grep/ripgrep: magenta path, green line number, bold red match
+const FILE_COLOR: &str = "\x1b[35m";
+const LINE_COLOR: &str = "\x1b[32m";
+const MATCH_COLOR: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Serialize)]
+struct Record<'a> {
+    path: String,
+    line: usize,
+    column: usize,
+    language: &'static str,
+    kind: &'static str,
+    score: i32,
+    text: &'a str,
+    #[serde(skip_serializing_if = "<[DocTag]>::is_empty")]
+    tags: &'a [DocTag],
+}
+
+impl<'a> Record<'a> {
+    fn from_result(result: &SearchResult<'a>) -> Self {
+        let comment = result.comment;
+        Record {
+            path: format!("{}{}", comment.file_path.to_string_lossy(), comment.cell_anchor()),
+            line: comment.line,
+            column: comment.column,
+            language: comment.language.name(),
+            kind: comment.kind.name(),
+            score: result.score,
+            text: comment.text,
+            tags: &comment.tags,
+        }
+    }
+}
+
+// `color` only affects the plain format, the others are meant for machines
+pub fn write_results<W: Write>(out: &mut W, query: &str, results: &[SearchResult], format: Format, color: bool) -> io::Result<()> {
+    match format {
+        Format::Plain if color => write_plain_colored(out, query, results),
+        Format::Plain => write_plain(out, query, results),
+        Format::Json => write_json(out, results),
+        Format::Jsonl => write_jsonl(out, results),
+        Format::Csv => write_csv(out, results),
+        Format::PathLine => write_locations(out, results, false),
+        Format::PathLineCol => write_locations(out, results, true),
+    }
+}
+
+fn write_plain<W: Write>(out: &mut W, query: &str, results: &[SearchResult]) -> io::Result<()> {
+    writeln!(out, "Search results for {}: {} matches", query, results.len())?;
+    for result in results {
+        let comment = result.comment;
+        writeln!(out, "{}{}:{}: {}", comment.file_name, comment.cell_anchor(), comment.line, comment.text.trim())?;
+    }
+    Ok(())
+}
+
+fn write_plain_colored<W: Write>(out: &mut W, query: &str, results: &[SearchResult]) -> io::Result<()> {
+    writeln!(out, "Search results for {}: {} matches", query, results.len())?;
+    for result in results {
+        let comment = result.comment;
+        write!(out, "{}{}{}{}:{}{}{}: ", FILE_COLOR, comment.file_name, comment.cell_anchor(), RESET, LINE_COLOR, comment.line, RESET)?;
+        for (segment, is_match) in result.trimmed_segments() {
+            if is_match {
+                write!(out, "{}{}{}", MATCH_COLOR, segment, RESET)?;
+            } else {
+                write!(out, "{}", segment)?;
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn write_locations<W: Write>(out: &mut W, results: &[SearchResult], with_column: bool) -> io::Result<()> {
+    for result in results {
+        let comment = result.comment;
+        write!(out, "{}{}:{}", comment.file_path.display(), comment.cell_anchor(), comment.line)?;
+        if with_column {
+            write!(out, ":{}", comment.column)?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn write_json<W: Write>(out: &mut W, results: &[SearchResult]) -> io::Result<()> {
+    let records: Vec<Record> = results.iter().map(Record::from_result).collect();
+    serde_json::to_writer_pretty(&mut *out, &records)?;
+    writeln!(out)
+}
+
+fn write_jsonl<W: Write>(out: &mut W, results: &[SearchResult]) -> io::Result<()> {
+    for result in results {
+        serde_json::to_writer(&mut *out, &Record::from_result(result))?;
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn write_csv<W: Write>(out: &mut W, results: &[SearchResult]) -> io::Result<()> {
+    writeln!(out, "path,line,column,language,kind,score,text")?;
+    for result in results {
+        let record = Record::from_result(result);
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{}",
+            csv_field(&record.path),
+            record.line,
+            record.column,
+            record.language,
+            record.kind,
+            record.score,
+            csv_field(record.text),
+        )?;
+    }
+    Ok(())
+}
+
+// RFC 4180: quote fields containing separators, quotes or line breaks
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
@@ -1,4 +1,8 @@
+use std::ops::Range;
+
 use super::engine::Comment;
+use super::fuzzy::fuzzy_match;
+use super::tags::{normalize_tag, DocTag};
 
 #[derive(Debug, Clone)]
 pub enum SearchMode {
@@ -6,45 +10,125 @@ pub enum SearchMode {
     Or,  // any term can match
 }
 
-pub fn search<'a>(comments: &'a [Comment], query: &str, mode: SearchMode) -> Vec<&'a Comment<'a>> {
+#[derive(Clone)]
+pub struct SearchResult<'a> {
+    pub comment: &'a Comment<'a>,
+    pub score: i32,
+    pub ranges: Vec<Range<usize>>, // sorted, non-overlapping byte ranges of matched text in comment.text
+}
+
+impl<'a> SearchResult<'a> {
+    /// The trimmed comment text split into `(segment, is_match)` pieces, for highlighting.
+    pub fn trimmed_segments(&self) -> Vec<(&'a str, bool)> {
+        let text = self.comment.text;
+        let start = text.len() - text.trim_start().len();
+        let end = text.trim_end().len().max(start);
+
+        let mut segments = Vec::new();
+        let mut pos = start;
+        for range in &self.ranges {
+            let match_start = range.start.clamp(pos, end);
+            let match_end = range.end.clamp(match_start, end);
+            if match_start > pos {
+                segments.push((&text[pos..match_start], false));
+            }
+            if match_end > match_start {
+                segments.push((&text[match_start..match_end], true));
+            }
+            pos = match_end;
+        }
+        if pos < end {
+            segments.push((&text[pos..end], false));
+        }
+
+        segments
+    }
+}
+
+pub fn search<'a>(comments: &'a [Comment], query: &str, mode: SearchMode) -> Vec<SearchResult<'a>> {
     if query.trim().is_empty() {
         return Vec::new();
     }
 
-    let terms: Vec<&str> = query.split_whitespace().collect();
+    // @tag terms keep only the doc comments with such a tag, and the other
+    // terms then have to match within one of those tags
+    let (tag_terms, terms): (Vec<&str>, Vec<&str>) = query.split_whitespace().partition(|term| is_tag_term(term));
+    let tags: Vec<String> = tag_terms.iter().map(|term| normalize_tag(&term[1..])).collect();
 
-    let mut results: Vec<&'a Comment<'a>> = comments
+    let mut results: Vec<SearchResult<'a>> = comments
         .iter()
-        .filter(|comment| match mode {
-            SearchMode::And => terms.iter().all(|term| {
-                comment.text.to_lowercase().contains(&term.to_lowercase())
-                    || comment.file_name.to_lowercase().contains(&term.to_lowercase())
-            }),
-            SearchMode::Or => terms.iter().any(|term| {
-                comment.text.to_lowercase().contains(&term.to_lowercase())
-                    || comment.file_name.to_lowercase().contains(&term.to_lowercase())
-            }),
-        })
+        .filter_map(|comment| score_comment(comment, &terms, &tags, &mode))
         .collect();
 
-    results.sort_by_key(|comment| calculate_score(comment, &terms)); // current scoring to replace
+    // best score first, shorter comments win ties
+    results.sort_by_key(|result| (std::cmp::Reverse(result.score), result.comment.text.len()));
 
     results
 }
 
-fn calculate_score(comment: &Comment<'_>, terms: &[&str]) -> usize {
-    let file_name_lower = comment.file_name.to_lowercase();
-    let text_lower = comment.text.to_lowercase();
+fn is_tag_term(term: &str) -> bool {
+    term.len() > 1 && term.starts_with('@')
+}
+
+fn score_comment<'a>(comment: &'a Comment<'a>, terms: &[&str], tags: &[String], mode: &SearchMode) -> Option<SearchResult<'a>> {
+    if !tags.iter().all(|tag| comment.tags.iter().any(|doc_tag| doc_tag.tag == *tag)) {
+        return None;
+    }
+    let tag_texts: Vec<String> = comment.tags
+        .iter()
+        .filter(|doc_tag| tags.contains(&doc_tag.tag))
+        .map(DocTag::searchable_text)
+        .collect();
 
-    // base score
     let mut score = 0;
+    let mut positions = Vec::new();
+    // a query of nothing but @tags matches every comment with them
+    let mut matched_any = terms.is_empty();
+
     for term in terms {
-        if let Some(pos) = file_name_lower.find(term) {
-            score += 2000usize.saturating_sub(pos);
-        } else if let Some(pos) = text_lower.find(term) {
-            score += 1000usize.saturating_sub(pos);
+        let in_tags = tags.is_empty() || tag_texts.iter().any(|text| fuzzy_match(text, term).is_some());
+        let text_match = fuzzy_match(comment.text, term).filter(|_| in_tags);
+        // the file name can't stand in for a term that's scoped to tags
+        let name_match = fuzzy_match(comment.file_name, term).filter(|_| tags.is_empty());
+
+        let term_score = match (&text_match, &name_match) {
+            (Some(t), Some(n)) => t.score.max(n.score),
+            (Some(t), None) => t.score,
+            (None, Some(n)) => n.score,
+            (None, None) => match mode {
+                SearchMode::And => return None,
+                SearchMode::Or => continue,
+            },
+        };
+
+        matched_any = true;
+        score += term_score;
+        if let Some(m) = text_match {
+            positions.extend(m.positions);
+        }
+    }
+
+    if !matched_any {
+        return None;
+    }
+
+    positions.sort_unstable();
+    positions.dedup();
+
+    Some(SearchResult { comment, score, ranges: merge_positions(comment.text, &positions) })
+}
+
+// turns sorted char offsets into byte ranges, joining adjacent chars
+fn merge_positions(text: &str, positions: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+
+    for &pos in positions {
+        let char_len = text[pos..].chars().next().map_or(1, char::len_utf8);
+        match ranges.last_mut() {
+            Some(last) if last.end == pos => last.end = pos + char_len,
+            _ => ranges.push(pos..pos + char_len),
         }
     }
 
-    usize::MAX - score
+    ranges
 }
@@ -1,33 +1,73 @@
 // handle loading file contents
-use std::path::{ PathBuf };
-use std::fs;
+use std::path::{ Path, PathBuf };
+use std::fs::{self, File};
+use std::io::Read;
+use std::time::UNIX_EPOCH;
+
+use super::notebook::{self, Cell};
+use super::parallel;
+use super::parser::{detect_language, detect_language_from_shebang, Language};
 
 pub struct SourceFile {
     pub name: String,
     pub path: PathBuf,
     pub content: String,
+    pub language: Language,
+    pub modified: u64, // mtime in nanoseconds since the epoch, 0 if unknown
+    pub size: u64,     // size on disk, before line ending normalization
+    pub cells: Vec<Cell>, // a notebook's cells, empty for other files
+}
+
+pub fn load_files(paths: &[PathBuf], threads: usize) -> Vec<SourceFile> {
+    parallel::flat_map(paths, threads, |path| load_file(path))
 }
 
-pub fn load_files(paths: &[PathBuf]) -> Vec<SourceFile> {
-    let mut files = Vec::new();
-    
-    for path in paths {
-        if let Ok(content) = fs::read_to_string(path) {
-            let name = path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or_default()
-                .to_string();
-            files.push(SourceFile { 
-                name,
-                path: path.clone(),
-                content: normalize_line_endings(&content)
-            });
-        }
+// files that aren't recognized by name are only kept if they start with a
+// shebang for a supported language. Notebooks get the text of their cells
+fn load_file(path: &Path) -> Option<SourceFile> {
+    let is_notebook = notebook::is_notebook(path);
+    let known_language = detect_language(path);
+    if known_language.is_none() && !is_notebook && !has_shebang(path) {
+        return None;
     }
 
-    files
+    let content = fs::read_to_string(path).ok()?;
+    let (content, language, cells) = if is_notebook {
+        let notebook = notebook::parse(&content)?;
+        (notebook.content, notebook.language, notebook.cells)
+    } else {
+        let language = known_language.or_else(|| detect_language_from_shebang(&content))?;
+        (normalize_line_endings(&content), language, Vec::new())
+    };
+    let metadata = fs::metadata(path).ok()?;
+    let modified = metadata.modified().ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos() as u64)
+        .unwrap_or(0);
+    let name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default()
+        .to_string();
+
+    Some(SourceFile {
+        name,
+        path: path.to_path_buf(),
+        content,
+        language,
+        modified,
+        size: metadata.len(),
+        cells,
+    })
+}
+
+// checks the first two bytes, so unrelated files (binaries, LICENSE) aren't read whole
+fn has_shebang(path: &Path) -> bool {
+    let mut magic = [0; 2];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut magic))
+        .is_ok_and(|_| &magic == b"#!")
 }
 
-fn normalize_line_endings(content: &str) -> String {
+pub fn normalize_line_endings(content: &str) -> String {
     content.replace("\r\n", "\n").replace("\r", "\n")
 }
\ No newline at end of file
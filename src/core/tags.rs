@@ -0,0 +1,342 @@
+// block tags of doc comments and docstrings: Javadoc, JSDoc and Doxygen
+// `@param x ...` lines, reST `:param x:` fields and Google and NumPy style
+// docstring sections. Tag names are lowercased and their aliases folded, so
+// JSDoc's @returns is a `return` tag and a Google `Raises:` entry a `throws` one
+use serde::Serialize;
+
+use super::parser::CommentType;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DocTag {
+    pub tag: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>, // the parameter, property or exception documented
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<String>,
+    pub text: String,
+}
+
+impl DocTag {
+    // everything a query scoped to this tag can match
+    pub fn searchable_text(&self) -> String {
+        [self.name.as_deref(), self.type_name.as_deref(), Some(self.text.as_str())]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+// Google and NumPy section titles and the tags their entries become
+const SECTIONS: &[(&str, &str)] = &[
+    ("args", "param"),
+    ("arguments", "param"),
+    ("params", "param"),
+    ("parameters", "param"),
+    ("other parameters", "param"),
+    ("keyword args", "param"),
+    ("keyword arguments", "param"),
+    ("attributes", "attribute"),
+    ("return", "return"),
+    ("returns", "return"),
+    ("yield", "yields"),
+    ("yields", "yields"),
+    ("raises", "throws"),
+    ("exceptions", "throws"),
+    ("throws", "throws"),
+    ("see also", "see"),
+    ("deprecated", "deprecated"),
+];
+
+pub fn normalize_tag(tag: &str) -> String {
+    let tag = tag.to_lowercase();
+    match tag.as_str() {
+        "arg" | "argument" | "parameter" => "param".to_string(),
+        "returns" => "return".to_string(),
+        "throw" | "exception" | "raise" | "raises" => "throws".to_string(),
+        "yield" => "yields".to_string(),
+        "seealso" => "see".to_string(),
+        _ => tag,
+    }
+}
+
+// tags followed by the name of what they document
+fn takes_name(tag: &str) -> bool {
+    matches!(tag, "param" | "tparam" | "typeparam" | "template" | "property" | "prop" | "throws")
+}
+
+pub fn parse_tags(text: &str, kind: CommentType) -> Vec<DocTag> {
+    let lines = match kind {
+        CommentType::Doc => comment_lines(text),
+        CommentType::Docstring => docstring_lines(text),
+        _ => return Vec::new(),
+    };
+    // Doxygen's \param, but a \ starting a docstring line is more likely LaTeX
+    let backslash_tags = kind == CommentType::Doc;
+
+    let mut tags = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim();
+
+        if let Some((tag, end)) = section_at(&lines, i) {
+            tags.extend(tag);
+            i = end;
+        } else if let Some(mut tag) = tag_line(line, backslash_tags) {
+            // the description runs on until a blank line or the next tag
+            i += 1;
+            while i < lines.len() && !lines[i].trim().is_empty() && !is_tag_start(&lines, i, backslash_tags) {
+                append(&mut tag.text, lines[i]);
+                i += 1;
+            }
+            tags.push(tag);
+        } else {
+            i += 1;
+        }
+    }
+
+    tags
+}
+
+fn is_tag_start(lines: &[&str], i: usize, backslash_tags: bool) -> bool {
+    tag_line(lines[i].trim(), backslash_tags).is_some() || section_at(lines, i).is_some()
+}
+
+// the lines of a doc comment without the comment markers and the `*` gutter
+// of Javadoc-style blocks
+fn comment_lines(text: &str) -> Vec<&str> {
+    const OPENERS: &[&str] = &["/**", "/*!", "///", "//!", "(**", "{-|", "{-^", "-- |", "-- ^", "--"];
+    const CLOSERS: &[&str] = &["*/", "*)", "-}"];
+
+    text.lines()
+        .map(|line| {
+            let mut line = line.trim();
+            if let Some(rest) = OPENERS.iter().find_map(|opener| line.strip_prefix(opener)) {
+                line = rest;
+            } else if let Some(rest) = line.strip_prefix('*').filter(|rest| !rest.starts_with('/')) {
+                line = rest;
+            }
+            if let Some(rest) = CLOSERS.iter().find_map(|closer| line.strip_suffix(closer)) {
+                line = rest;
+            }
+            line.strip_prefix(' ').unwrap_or(line)
+        })
+        .collect()
+}
+
+// the lines of a docstring without its quotes (and any string prefix or sigil),
+// indentation kept since Google and NumPy sections depend on it
+fn docstring_lines(text: &str) -> Vec<&str> {
+    let body = text.trim_start_matches(|c: char| c.is_ascii_alphabetic() || c == '~');
+    body.trim_matches(['"', '\'']).lines().collect()
+}
+
+// `@tag`, `\tag` (with `backslash_tags`), reST's `:tag name:` fields and the
+// `.. deprecated::` and `.. seealso::` directives
+fn tag_line(line: &str, backslash_tags: bool) -> Option<DocTag> {
+    if let Some(field) = line.strip_prefix(':') {
+        let (head, text) = field.split_once(':')?;
+        // a role like :class:`Foo` starting a sentence isn't a field
+        if !text.is_empty() && !text.starts_with(char::is_whitespace) {
+            return None;
+        }
+        let words: Vec<&str> = head.split_whitespace().collect();
+        let (tag, name, type_name) = match words.as_slice() {
+            [tag] => (tag, None, None),
+            [tag, name] => (tag, Some(name), None),
+            [tag, type_name, name] => (tag, Some(name), Some(type_name)),
+            _ => return None,
+        };
+        return Some(DocTag {
+            tag: normalize_tag(tag),
+            name: name.map(|name| name.to_string()),
+            type_name: type_name.map(|type_name| type_name.to_string()),
+            text: text.trim().to_string(),
+        });
+    }
+    if let Some(directive) = line.strip_prefix("..") {
+        let (name, text) = directive.trim_start().split_once("::")?;
+        if name != "deprecated" && name != "seealso" {
+            return None;
+        }
+        return Some(DocTag { tag: normalize_tag(name), name: None, type_name: None, text: text.trim().to_string() });
+    }
+
+    let rest = line.strip_prefix('@').or_else(|| line.strip_prefix('\\').filter(|_| backslash_tags))?;
+    let tag_len = rest.find(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '-').unwrap_or(rest.len());
+    // part of an address like @example.com, not a tag
+    if tag_len == 0 || (!rest[tag_len..].is_empty() && !rest[tag_len..].starts_with(char::is_whitespace)) {
+        return None;
+    }
+    let tag = normalize_tag(&rest[..tag_len]);
+    let mut rest = rest[tag_len..].trim_start();
+
+    // JSDoc's {Type}
+    let mut type_name = None;
+    if rest.starts_with('{') {
+        let end = braced_end(rest);
+        type_name = Some(rest[1..end].trim_end_matches('}').trim().to_string());
+        rest = rest[end..].trim_start();
+    }
+
+    // JSDoc's @throws {Error} has no name, Javadoc's @throws IOException does
+    let mut name = None;
+    if takes_name(&tag) && !(tag == "throws" && type_name.is_some()) && !rest.is_empty() {
+        let name_len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        // [optional] and [name=default] parameters
+        let word = rest[..name_len].trim_start_matches('[').trim_end_matches(']');
+        name = Some(word.split('=').next().unwrap_or(word).to_string());
+        rest = rest[name_len..].trim_start();
+    }
+
+    // JSDoc's @param x - the x
+    let text = rest.strip_prefix("- ").unwrap_or(rest);
+    Some(DocTag { tag, name, type_name, text: text.to_string() })
+}
+
+// the end of the {...} at the start of `text`, nested braces included
+fn braced_end(text: &str) -> usize {
+    let mut depth = 0;
+    for (i, ch) in text.char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            },
+            _ => {},
+        }
+    }
+    text.len()
+}
+
+// a Google (`Args:`) or NumPy (`Parameters` over a `----------` line) section
+// starting at line `i`: its tags and the line after it
+fn section_at(lines: &[&str], i: usize) -> Option<(Vec<DocTag>, usize)> {
+    let line = lines[i];
+    let title = line.trim();
+    let indent = line.len() - line.trim_start().len();
+    let underlined = lines.get(i + 1).is_some_and(|next| is_underline(next));
+
+    if underlined && !title.is_empty() && title.chars().all(|c| c.is_alphabetic() || c == ' ') {
+        // NumPy: the body runs until the next underlined title, entries are
+        // at the title's indentation and their descriptions below them.
+        // Sections like Notes don't become tags, their lines are read as usual
+        let Some(tag) = section_tag(title) else {
+            return Some((Vec::new(), i + 2));
+        };
+        let start = i + 2;
+        let mut end = start;
+        while end < lines.len() && !lines.get(end + 1).is_some_and(|next| is_underline(next)) {
+            end += 1;
+        }
+        return Some((section_tags(tag, &lines[start..end], Style::NumPy), end));
+    }
+
+    let title = title.strip_suffix(':')?;
+    let tag = section_tag(title)?;
+    // Google: the body is indented below the title
+    let start = i + 1;
+    let mut end = start;
+    while end < lines.len() && (lines[end].trim().is_empty() || lines[end].len() - lines[end].trim_start().len() > indent) {
+        end += 1;
+    }
+    if end == start {
+        return None;
+    }
+    Some((section_tags(tag, &lines[start..end], Style::Google), end))
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && line.chars().all(|c| c == '-')
+}
+
+fn section_tag(title: &str) -> Option<&'static str> {
+    let title = title.to_lowercase();
+    SECTIONS.iter().find(|(section, _)| *section == title).map(|(_, tag)| *tag)
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Style {
+    Google,
+    NumPy,
+}
+
+fn section_tags(tag: &str, body: &[&str], style: Style) -> Vec<DocTag> {
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+    let Some(entry_indent) = body.iter().filter(|line| !line.trim().is_empty()).map(|line| indent_of(line)).min() else {
+        return Vec::new();
+    };
+
+    // free text sections, and Google's Returns, which is one description
+    let whole = matches!(tag, "see" | "deprecated") || style == Style::Google && matches!(tag, "return" | "yields");
+    if whole {
+        let mut text = String::new();
+        for line in body {
+            append(&mut text, line);
+        }
+        let mut tag = DocTag { tag: tag.to_string(), name: None, type_name: None, text };
+        // Returns: bool: whether it worked
+        if let Some((type_name, rest)) = tag.text.split_once(": ")
+            && matches!(tag.tag.as_str(), "return" | "yields")
+            && !type_name.contains(' ')
+        {
+            tag = DocTag { type_name: Some(type_name.to_string()), text: rest.to_string(), ..tag };
+        }
+        return vec![tag];
+    }
+
+    let mut tags: Vec<DocTag> = Vec::new();
+    for line in body {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indent_of(line) > entry_indent {
+            if let Some(last) = tags.last_mut() {
+                append(&mut last.text, line);
+            }
+            continue;
+        }
+
+        let entry = line.trim();
+        let (name, type_name, text) = match style {
+            // x : int
+            Style::NumPy => match entry.split_once(" :") {
+                Some((name, type_name)) => (Some(name.trim()), Some(type_name.trim()).filter(|t| !t.is_empty()), ""),
+                None if matches!(tag, "return" | "yields") => (None, Some(entry), ""),
+                None => (Some(entry), None, ""),
+            },
+            // x (int): the x, or ValueError: if it isn't
+            Style::Google => match entry.split_once(':') {
+                Some((head, text)) => match head.split_once('(') {
+                    Some((name, type_name)) => (Some(name.trim()), Some(type_name.trim_end().trim_end_matches(')')), text.trim()),
+                    None => (Some(head.trim()), None, text.trim()),
+                },
+                None => (None, None, entry),
+            },
+        };
+        tags.push(DocTag {
+            tag: tag.to_string(),
+            name: name.map(|name| name.to_string()),
+            type_name: type_name.map(|type_name| type_name.to_string()),
+            text: text.to_string(),
+        });
+    }
+
+    tags
+}
+
+// joins description lines with single spaces
+fn append(text: &mut String, line: &str) {
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+    if !text.is_empty() {
+        text.push(' ');
+    }
+    text.push_str(line);
+}
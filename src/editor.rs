@@ -0,0 +1,63 @@
+// launching the user's editor at a file location
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+// overrides the argument template below for any editor, e.g. "+{line} {file}"
+pub const TEMPLATE_ENV_VAR: &str = "FUZC_EDITOR_TEMPLATE";
+
+#[cfg(windows)]
+const DEFAULT_EDITOR: &str = "notepad";
+#[cfg(not(windows))]
+const DEFAULT_EDITOR: &str = "vi";
+
+// used for editors we don't know how to jump to a line in
+const FALLBACK_TEMPLATE: &str = "{file}";
+
+fn template_for(program: &str) -> &'static str {
+    let name = Path::new(program)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(program);
+
+    match name {
+        "vi" | "vim" | "nvim" | "gvim" | "mvim" | "view" => "+{line} {file}",
+        "emacs" | "emacsclient" | "kak" => "+{line}:{column} {file}",
+        "nano" | "pico" => "+{line},{column} {file}",
+        "code" | "code-insiders" | "codium" | "cursor" => "--goto {file}:{line}:{column}",
+        "hx" | "helix" | "subl" | "zed" => "{file}:{line}:{column}",
+        _ => FALLBACK_TEMPLATE,
+    }
+}
+
+/// Builds the command for $VISUAL (or $EDITOR) to open `path` at `line`/`column`.
+/// The editor variable may include arguments, e.g. `EDITOR="code -w"`.
+pub fn editor_command(path: &Path, line: usize, column: usize) -> Command {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_string());
+
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR);
+
+    let mut command = Command::new(program);
+    command.args(parts);
+
+    let template = env::var(TEMPLATE_ENV_VAR)
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| template_for(program).to_string());
+
+    let file = path.to_string_lossy();
+    for arg in template.split_whitespace() {
+        command.arg(
+            arg.replace("{line}", &line.to_string())
+                .replace("{column}", &column.to_string())
+                .replace("{file}", &file),
+        );
+    }
+
+    command
+}
@@ -1,18 +1,26 @@
 mod core;
+mod editor;
 mod tui;
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
+use std::io::{IsTerminal, Write};
 use std::path::Path;
+use core::index::CommentIndex;
+use core::parser::Language;
+use core::present::{ColorChoice, Format};
 use core::search;
 
 #[derive(Parser)]
 #[command(name = "fuzc")]
 #[command(about = "a fuzzy finder for code comments")]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     #[arg(long)]
     query: Option<String>, // Optional query for CLI mode
 
-    #[arg(short, long, default_value = ".")]
+    #[arg(short, long, default_value = ".", global = true)]
     directory: String, // directory to search (default: current directory)
 
     #[arg(long)]
@@ -20,11 +28,103 @@ struct Args {
 
     #[arg(long)]
     cli: bool, // Flag to force CLI mode
+
+    #[arg(long, value_enum)]
+    format: Option<Format>, // output format (default: plain in CLI mode, path-line for TUI selections)
+
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto)]
+    color: ColorChoice, // highlight matches in plain output, honors NO_COLOR in auto mode
+
+    #[arg(long)]
+    print: bool, // TUI: Enter prints the selection to stdout and exits (implied when stdout isn't a terminal)
+
+    #[arg(long, value_delimiter = ',', global = true)]
+    lang: Vec<String>, // only search these languages, e.g. --lang java,py (default: all)
+
+    #[arg(long)]
+    markdown_cells: bool, // also search the markdown cells of Jupyter notebooks, as doc comments
+
+    #[arg(long, global = true)]
+    no_ignore: bool, // don't honor .gitignore, .ignore and .fuzcignore files
+
+    #[arg(long, global = true)]
+    threads: Option<usize>, // worker threads for walking and parsing (default: number of CPUs)
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "build or refresh the comment index in <directory>/.fuzc")]
+    Index,
+}
+
+// resolved after parsing, languages.toml files can define more of them
+fn parse_languages(names: &[String]) -> Result<Vec<Language>, String> {
+    names
+        .iter()
+        .map(|name| Language::from_name(name).ok_or_else(|| format!("unsupported language: {}", name)))
+        .collect()
 }
 
 fn main() {
     let args = Args::parse();
-    
+
+    let directory = Path::new(&args.directory);
+    if let Err(e) = core::parser::load_languages(&directory.join(core::index::INDEX_DIR)) {
+        eprintln!("Error reading language definitions: {}", e);
+        std::process::exit(1);
+    }
+    let languages = match parse_languages(&args.lang) {
+        Ok(languages) => languages,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let threads = args.threads.unwrap_or_else(core::parallel::default_threads).max(1);
+    let walk_options = core::discover::WalkOptions {
+        use_ignore_files: !args.no_ignore,
+        threads,
+    };
+    let file_paths = core::discover::find_all_source_files(directory, &walk_options);
+    let file_paths = core::discover::filter_by_language(file_paths, &languages);
+    let mut files = core::source::load_files(&file_paths, threads);
+    // scripts without an extension only get their language from the shebang
+    files.retain(|file| languages.is_empty() || languages.contains(&file.language));
+
+    // searches only use the index once `fuzc index` has created it
+    let building_index = matches!(args.command, Some(Command::Index));
+    let index = if building_index || core::index::exists(directory) {
+        let previous = core::index::load(directory);
+        let (index, stats) = CommentIndex::refresh(previous.as_ref(), directory, &files, threads, !building_index, &languages);
+
+        if stats.changed
+            && let Err(e) = index.save()
+        {
+            eprintln!("Error writing index: {}", e);
+            if building_index {
+                std::process::exit(1);
+            }
+        }
+
+        if building_index {
+            println!(
+                "Indexed {} files ({} parsed, {} unchanged) into {}",
+                stats.files,
+                stats.parsed,
+                stats.files - stats.parsed,
+                core::index::index_path(directory).display()
+            );
+            return;
+        }
+
+        Some(index)
+    } else {
+        None
+    };
+
+    let comments = core::engine::extract_comments(&files, threads, index.as_ref(), args.markdown_cells);
+
     // If --cli flag is used OR query is provided, run CLI mode
     if args.cli || args.query.is_some() {
         // CLI mode - need a query
@@ -35,29 +135,47 @@ fn main() {
                 std::process::exit(1);
             }
         };
-        
-        let directory = Path::new(&args.directory);
+
         let search_mode = if args.strict {
             search::SearchMode::And
         } else {
             search::SearchMode::Or
         };
-        
-        let file_paths = core::discover::find_source_files(&directory, "java");
-        let files = core::source::load_files(&file_paths);
-        let comments = core::engine::extract_comments(&files);
 
         let results = search::search(&comments, &query, search_mode);
-
-        println!("Search results for {}: {} matches", &query, results.len());
-        for result in results {
-            println!("{}:{}: {}", result.file_name, result.line, result.text.trim());
-        }
+        print_results(&query, &results, args.format.unwrap_or(Format::Plain), args.color);
     } else {
         // Default: TUI mode
-        if let Err(e) = tui::run_tui() {
-            eprintln!("TUI error: {}", e);
-            std::process::exit(1);
+        let print_on_accept = args.print || !std::io::stdout().is_terminal();
+
+        match tui::run_tui(&files, &comments, print_on_accept) {
+            Ok(Some(selection)) => {
+                if selection.results.is_empty() {
+                    std::process::exit(1);
+                }
+                print_results(&selection.query, &selection.results, args.format.unwrap_or(Format::PathLine), args.color);
+            }
+            // cancelled with Esc/Ctrl+C: exit like fzf does so scripts can tell
+            Ok(None) if print_on_accept => std::process::exit(130),
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("TUI error: {}", e);
+                std::process::exit(1);
+            }
         }
     }
+}
+
+fn print_results(query: &str, results: &[search::SearchResult], format: Format, color: ColorChoice) {
+    let mut stdout = std::io::stdout().lock();
+    let written = core::present::write_results(&mut stdout, query, results, format, color.enabled())
+        .and_then(|_| stdout.flush());
+
+    // a closed pipe (e.g. `| head`) is not an error worth reporting
+    if let Err(e) = written
+        && e.kind() != std::io::ErrorKind::BrokenPipe
+    {
+        eprintln!("Error writing results: {}", e);
+        std::process::exit(1);
+    }
 }
\ No newline at end of file
@@ -5,55 +5,110 @@ use ratatui::Frame;
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
-    style::{Style, Color},
+    style::{Color, Modifier, Style},
     Terminal,
 };
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::path::Path;
 use crate::core::engine::Comment;
-use crate::core::search::SearchMode;
+use crate::core::search::{SearchMode, SearchResult};
+use crate::core::source::SourceFile;
+use crate::editor::editor_command;
+
+// below this width the preview goes under the results instead of beside them
+const SIDE_PREVIEW_MIN_WIDTH: u16 = 100;
+
+// the UI is drawn on the terminal device rather than stdout, so stdout
+// only ever carries the selection and can be piped
+type TtyTerminal = Terminal<CrosstermBackend<File>>;
 
 pub struct TuiState<'a> {
     query: String,
-    results: Vec<&'a Comment<'a>>,
+    results: Vec<SearchResult<'a>>,
+    marked: Vec<SearchResult<'a>>, // Tab-marked results, in the order they were marked
     selected: usize,
     should_quit: bool,
     strict_mode: bool,
+    show_preview: bool,
+    print_on_accept: bool, // Enter exits and returns the selection instead of opening the editor
+    accepted: bool,
+    open_selected: bool, // set on Enter, handled by the main loop which owns the terminal
+    status_message: Option<String>,
+    sources: HashMap<&'a Path, &'a SourceFile>, // for looking up preview content
 }
 
-pub fn run_tui() -> Result<(), Box<dyn std::error::Error>> {
+// what the user picked when leaving the TUI with Enter in print mode
+pub struct Selection<'a> {
+    pub query: String,
+    pub results: Vec<SearchResult<'a>>,
+}
+
+pub fn run_tui<'a>(
+    files: &'a [SourceFile],
+    comments: &'a [Comment<'a>],
+    print_on_accept: bool,
+) -> Result<Option<Selection<'a>>, Box<dyn std::error::Error>> {
     // setup
+    let mut tty = open_tty()?;
     enable_raw_mode()?;
-    std::io::stdout().execute(EnterAlternateScreen)?;
+    tty.execute(EnterAlternateScreen)?;
 
-    let backend = CrosstermBackend::new(std::io::stdout());
+    let backend = CrosstermBackend::new(tty);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run_tui_loop(&mut terminal);
+    let result = run_tui_loop(&mut terminal, files, comments, print_on_accept);
 
     disable_raw_mode()?;
-    std::io::stdout().execute(LeaveAlternateScreen)?;
+    terminal.backend_mut().execute(LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
 
     result
 }
 
-fn run_tui_loop(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> Result<(), Box<dyn std::error::Error>> {
-   let directory = std::path::Path::new(".");
-   let file_paths = crate::core::discover::find_all_source_files(&directory);
-   let files = crate::core::source::load_files(&file_paths);
-   let comments = crate::core::engine::extract_comments(&files);
+fn open_tty() -> std::io::Result<File> {
+    #[cfg(windows)]
+    let path = "CONOUT$";
+    #[cfg(not(windows))]
+    let path = "/dev/tty";
+
+    OpenOptions::new().read(true).write(true).open(path)
+}
 
+fn run_tui_loop<'a>(
+    terminal: &mut TtyTerminal,
+    files: &'a [SourceFile],
+    comments: &'a [Comment<'a>],
+    print_on_accept: bool,
+) -> Result<Option<Selection<'a>>, Box<dyn std::error::Error>> {
    let mut state = TuiState {
         query: String::new(),
         results: Vec::new(),
+        marked: Vec::new(),
         selected: 0,
         should_quit: false,
         strict_mode: false,
+        show_preview: false,
+        print_on_accept,
+        accepted: false,
+        open_selected: false,
+        status_message: None,
+        sources: files.iter().map(|file| (file.path.as_path(), file)).collect(),
    };
 
    while !state.should_quit {
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                handle_key_event(& mut state, &comments, key);
+        if event::poll(std::time::Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+        {
+            handle_key_event(& mut state, comments, key);
+        }
+
+        if state.open_selected {
+            state.open_selected = false;
+            if let Some(result) = state.results.get(state.selected) {
+                state.status_message = open_in_editor(terminal, result.comment)?;
             }
         }
 
@@ -61,8 +116,20 @@ fn run_tui_loop(terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>) -> R
             render_ui(frame, &state);
         })?;
    }
-   
-   Ok(())
+
+   if !state.accepted {
+       return Ok(None);
+   }
+
+   // marked results win over the one under the cursor
+   let results = if state.marked.is_empty() {
+       let selected = state.selected;
+       state.results.into_iter().nth(selected).into_iter().collect()
+   } else {
+       state.marked
+   };
+
+   Ok(Some(Selection { query: state.query, results }))
 }
 
 fn handle_key_event<'a>(state: &mut TuiState<'a>, comments: &'a [Comment<'a>], key: KeyEvent) {
@@ -70,31 +137,48 @@ fn handle_key_event<'a>(state: &mut TuiState<'a>, comments: &'a [Comment<'a>], k
     if key.kind != KeyEventKind::Press {
         return;
     }
-    
+    state.status_message = None;
+
     match key.code {
         KeyCode::Esc => {
             state.should_quit = true;
         }
-        KeyCode::Tab => {
+        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             state.strict_mode = !state.strict_mode;
             update_search_results(state, comments);
         }
+        KeyCode::Tab => {
+            toggle_mark(state);
+            if state.selected < state.results.len().saturating_sub(1) {
+                state.selected += 1;
+            }
+        }
+        KeyCode::BackTab => {
+            toggle_mark(state);
+            state.selected = state.selected.saturating_sub(1);
+        }
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             state.should_quit = true;
         }
+        KeyCode::Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            state.show_preview = !state.show_preview;
+        }
+        KeyCode::Enter if state.print_on_accept => {
+            state.accepted = true;
+            state.should_quit = true;
+        }
+        KeyCode::Enter => {
+            state.open_selected = true;
+        }
         KeyCode::Backspace => {
             state.query.pop();
             update_search_results(state, comments);
         }
-        KeyCode::Up => {
-            if state.selected > 0 {
-                state.selected -= 1;
-            }
+        KeyCode::Up if state.selected > 0 => {
+            state.selected -= 1;
         }
-        KeyCode::Down => {
-            if state.selected < state.results.len().saturating_sub(1) {
-                state.selected += 1;
-            }
+        KeyCode::Down if state.selected < state.results.len().saturating_sub(1) => {
+            state.selected += 1;
         }
         KeyCode::Char(c) => {
             state.query.push(c);
@@ -104,6 +188,54 @@ fn handle_key_event<'a>(state: &mut TuiState<'a>, comments: &'a [Comment<'a>], k
     }
 }
 
+fn toggle_mark(state: &mut TuiState) {
+    let Some(result) = state.results.get(state.selected) else {
+        return;
+    };
+
+    match state.marked.iter().position(|marked| is_same_comment(marked, result)) {
+        Some(pos) => {
+            state.marked.remove(pos);
+        }
+        None => state.marked.push(result.clone()),
+    }
+}
+
+fn is_same_comment(a: &SearchResult, b: &SearchResult) -> bool {
+    std::ptr::eq(a.comment, b.comment)
+}
+
+// suspends the TUI while the editor runs. Problems with the editor itself are
+// returned as a message for the status line, terminal errors are propagated
+fn open_in_editor(
+    terminal: &mut TtyTerminal,
+    comment: &Comment,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    // a notebook's cell lines don't match lines of its JSON
+    let (line, column) = match comment.cell {
+        Some(_) => (1, 1),
+        None => (comment.line, comment.column),
+    };
+    let mut command = editor_command(comment.file_path, line, column);
+
+    disable_raw_mode()?;
+    terminal.backend_mut().execute(LeaveAlternateScreen)?;
+
+    let status = command.status();
+
+    enable_raw_mode()?;
+    terminal.backend_mut().execute(EnterAlternateScreen)?;
+    terminal.clear()?;
+
+    let message = match status {
+        Ok(status) if status.success() => None,
+        Ok(status) => Some(format!("editor exited with {}", status)),
+        Err(e) => Some(format!("could not launch {:?}: {}", command.get_program(), e)),
+    };
+
+    Ok(message)
+}
+
 fn update_search_results<'a>(state: &mut TuiState<'a>, comments: &'a [Comment<'a>]) {
     let search_mode = if state.strict_mode {
         SearchMode::And 
@@ -137,8 +269,23 @@ fn render_ui(frame: &mut Frame, state: &TuiState) {
 
     // render search input
     render_search_input(frame, chunks[0], state);
-    // render Results
-    render_results_list(frame, chunks[1], state);
+    // render Results, with the preview beside or below them
+    if state.show_preview {
+        let direction = if chunks[1].width >= SIDE_PREVIEW_MIN_WIDTH {
+            Direction::Horizontal
+        } else {
+            Direction::Vertical
+        };
+        let panes = Layout::default()
+            .direction(direction)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
+
+        render_results_list(frame, panes[0], state);
+        render_preview(frame, panes[1], state);
+    } else {
+        render_results_list(frame, chunks[1], state);
+    }
     // render status line
     render_status_line(frame, chunks[2], state);
 }
@@ -154,11 +301,15 @@ fn render_search_input(frame: &mut Frame, area: Rect, state: &TuiState) {
 fn render_results_list(frame: &mut Frame, area: Rect, state: &TuiState) {
     let items: Vec<ListItem> = state.results
         .iter()
-        .map(|comment| {
-            ListItem::new(format!("{}:{}: {}",
-            comment.file_name,
-            comment.line,
-            comment.text.trim()))
+        .map(|result| {
+            let comment = result.comment;
+            let marker = if state.marked.iter().any(|marked| is_same_comment(marked, result)) {
+                "* "
+            } else {
+                "  "
+            };
+            let prefix = format!("{}{}{}:{}: ", marker, comment.file_name, comment.cell_anchor(), comment.line);
+            ListItem::new(highlighted_lines(prefix, result))
         })
         .collect();
 
@@ -171,8 +322,86 @@ fn render_results_list(frame: &mut Frame, area: Rect, state: &TuiState) {
     frame.render_stateful_widget(results_list, area, &mut ListState::default().with_selected(Some(state.selected)));
 }
 
+// the comment text with matched chars styled, split into lines for multi-line comments
+fn highlighted_lines<'a>(prefix: String, result: &SearchResult<'a>) -> Vec<Line<'a>> {
+    let match_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+
+    let mut lines = Vec::new();
+    let mut spans = vec![Span::raw(prefix)];
+
+    for (segment, is_match) in result.trimmed_segments() {
+        let style = if is_match { match_style } else { Style::default() };
+
+        for (i, piece) in segment.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Line::from(std::mem::take(&mut spans)));
+            }
+            if !piece.is_empty() {
+                spans.push(Span::styled(piece, style));
+            }
+        }
+    }
+    lines.push(Line::from(spans));
+
+    lines
+}
+
 fn render_status_line(frame: &mut ratatui::Frame, area: ratatui::layout::Rect, state: &TuiState) {
-    let status = format!("{} matches", state.results.len());
+    let mut status = format!("{} matches", state.results.len());
+    if !state.marked.is_empty() {
+        status.push_str(&format!(" | {} marked", state.marked.len()));
+    }
+    if let Some(message) = &state.status_message {
+        status.push_str(&format!(" | {}", message));
+    }
     let status_paragraph = Paragraph::new(status);
     frame.render_widget(status_paragraph, area);
-}
\ No newline at end of file
+}
+
+fn render_preview(frame: &mut Frame, area: Rect, state: &TuiState) {
+    let block = Block::default().borders(Borders::ALL);
+
+    let Some(comment) = state.results.get(state.selected).map(|result| result.comment) else {
+        frame.render_widget(block.title("Preview"), area);
+        return;
+    };
+    let Some(source) = state.sources.get(comment.file_path) else {
+        frame.render_widget(block.title("Preview"), area);
+        return;
+    };
+
+    // notebooks are previewed as their joined cells
+    let cell_offset = comment.cell.map_or(0, |cell| source.content[..source.cells[cell - 1].start].matches('\n').count());
+    let first_comment_line = cell_offset + comment.line;
+    let last_comment_line = first_comment_line + comment.text.lines().count().saturating_sub(1);
+
+    // scroll so the comment starts about a third of the way down
+    let height = area.height.saturating_sub(2) as usize;
+    let first_line = first_comment_line.saturating_sub(height / 3).max(1);
+    let gutter_width = (first_line + height).to_string().len();
+
+    let lines: Vec<Line> = source.content
+        .lines()
+        .enumerate()
+        .skip(first_line - 1)
+        .take(height)
+        .map(|(i, text)| {
+            let line_num = i + 1;
+            let in_comment = (first_comment_line..=last_comment_line).contains(&line_num);
+            let style = if in_comment {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default()
+            };
+
+            Line::from(vec![
+                Span::styled(format!("{:>width$} ", line_num, width = gutter_width), Style::default().fg(Color::DarkGray)),
+                Span::styled(text, style),
+            ])
+        })
+        .collect();
+
+    let title = format!("{}{}:{}", comment.file_path.display(), comment.cell_anchor(), comment.line);
+    let preview = Paragraph::new(lines).block(block.title(title));
+    frame.render_widget(preview, area);
+}